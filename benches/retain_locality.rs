@@ -0,0 +1,67 @@
+//! Compares inserts after a bulk `retain` with inserts after removing the
+//! same entries one by one in scattered order.
+//!
+//! `retain` rebuilds the free list in ascending index order, so the
+//! following inserts fill the arena front to back. Scattered removals
+//! leave the free list in removal order, so the inserts jump around.
+//!
+//! Run with `cargo bench --bench retain_locality`.
+
+use std::{hint::black_box, time::Instant};
+use typed_garena::{Arena, ID};
+
+const LEN: usize = 1 << 20;
+const ROUNDS: usize = 5;
+
+fn main() {
+    let mut rng = XorShift(0x9E37_79B9_7F4A_7C15);
+    let doomed: Vec<bool> = (0..LEN).map(|_| rng.next() % 2 == 0).collect();
+    let mut order: Vec<usize> = (0..LEN).filter(|&index| doomed[index]).collect();
+    for i in (1..order.len()).rev() {
+        order.swap(i, rng.next() as usize % (i + 1));
+    }
+
+    report("after retain", || {
+        let mut arena: Arena<u64> = (0..LEN as u64).collect();
+        arena.retain(|id, _| !doomed[id.index()]);
+        arena
+    });
+    report("after scattered removes", || {
+        let mut arena: Arena<u64> = (0..LEN as u64).collect();
+        let ids: Vec<ID<u64>> = arena.keys().collect();
+        for &index in &order {
+            arena.remove(ids[index]);
+        }
+        arena
+    });
+}
+
+/// Times inserting into the free slots of the arena made by `setup`, and
+/// then reading the new entries back in insertion order.
+fn report(name: &str, setup: impl Fn() -> Arena<u64>) {
+    let (mut insert, mut read) = (f64::MAX, f64::MAX);
+    for _ in 0..ROUNDS {
+        let mut arena = setup();
+        let free = arena.free_len();
+
+        let start = Instant::now();
+        let ids: Vec<_> = (0..free as u64).map(|t| arena.insert(t)).collect();
+        insert = insert.min(start.elapsed().as_secs_f64());
+
+        let start = Instant::now();
+        let sum: u64 = ids.iter().map(|&id| arena[id]).sum();
+        read = read.min(start.elapsed().as_secs_f64());
+        black_box(sum);
+    }
+    println!("{name:>24}: insert {:>8.2} ms, read back {:>8.2} ms", insert * 1e3, read * 1e3);
+}
+
+struct XorShift(u64);
+impl XorShift {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+}
@@ -0,0 +1,87 @@
+#[cfg(feature = "allocator_api")]
+pub use alloc::alloc::{Allocator, Global};
+#[cfg(feature = "allocator_api")]
+pub(crate) type Storage<E, A> = alloc::vec::Vec<E, A>;
+
+#[cfg(not(feature = "allocator_api"))]
+pub use stable::{Allocator, Global};
+#[cfg(not(feature = "allocator_api"))]
+pub(crate) use stable::Storage;
+
+pub(crate) type StorageIntoIter<E, A> = <Storage<E, A> as IntoIterator>::IntoIter;
+
+#[cfg(feature = "allocator_api")]
+pub(crate) fn storage<E>(vec: alloc::vec::Vec<E>) -> Storage<E, Global> {
+    vec
+}
+#[cfg(not(feature = "allocator_api"))]
+pub(crate) fn storage<E>(vec: alloc::vec::Vec<E>) -> Storage<E, Global> {
+    Storage::from(vec)
+}
+
+#[cfg(not(feature = "allocator_api"))]
+mod stable {
+    use alloc::vec::Vec;
+    use core::{
+        marker::PhantomData,
+        ops::{Deref, DerefMut},
+    };
+
+    /// Stands in for `core::alloc::Allocator` without the `allocator_api`
+    /// feature. Only [`Global`] implements it.
+    pub trait Allocator: sealed::Sealed {}
+
+    /// Stands in for `alloc::alloc::Global` without the `allocator_api` feature.
+    #[derive(Copy, Clone, Debug, Default)]
+    pub struct Global;
+    impl Allocator for Global {}
+
+    mod sealed {
+        pub trait Sealed {}
+        impl Sealed for super::Global {}
+    }
+
+    /// A `Vec` that carries an allocator type it doesn't use.
+    #[derive(Debug)]
+    pub(crate) struct Storage<E, A> {
+        vec: Vec<E>,
+        marker: PhantomData<A>,
+    }
+    impl<E, A> Deref for Storage<E, A> {
+        type Target = Vec<E>;
+        fn deref(&self) -> &Self::Target {
+            &self.vec
+        }
+    }
+    impl<E, A> DerefMut for Storage<E, A> {
+        fn deref_mut(&mut self) -> &mut Self::Target {
+            &mut self.vec
+        }
+    }
+    impl<E> From<Vec<E>> for Storage<E, Global> {
+        fn from(vec: Vec<E>) -> Self {
+            Self {
+                vec,
+                marker: PhantomData,
+            }
+        }
+    }
+    impl<E: Clone, A> Clone for Storage<E, A> {
+        fn clone(&self) -> Self {
+            Self {
+                vec: self.vec.clone(),
+                marker: PhantomData,
+            }
+        }
+        fn clone_from(&mut self, source: &Self) {
+            self.vec.clone_from(&source.vec);
+        }
+    }
+    impl<E, A> IntoIterator for Storage<E, A> {
+        type Item = E;
+        type IntoIter = alloc::vec::IntoIter<E>;
+        fn into_iter(self) -> Self::IntoIter {
+            self.vec.into_iter()
+        }
+    }
+}
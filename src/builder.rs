@@ -0,0 +1,56 @@
+use crate::{allocator::storage, Arena, ArenaGeneration, ArenaIndex, BuildError, Generation};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+/// Configures and creates an [`Arena`], see [`Arena::builder`].
+pub struct ArenaBuilder<T, I = usize, G = Generation> {
+    capacity: usize,
+    max_len: Option<usize>,
+    generation_start: Option<G>,
+    marker: PhantomData<fn() -> (T, I)>,
+}
+impl<T, I: ArenaIndex, G: ArenaGeneration> ArenaBuilder<T, I, G> {
+    pub(crate) fn new() -> Self {
+        Self {
+            capacity: 0,
+            max_len: None,
+            generation_start: None,
+            marker: PhantomData,
+        }
+    }
+
+    /// Sets the number of slots to allocate up front.
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+    /// Limits the arena to `max_len` live entries, like [`Arena::with_max_len`].
+    pub fn max_len(mut self, max_len: usize) -> Self {
+        self.max_len = Some(max_len);
+        self
+    }
+    /// Sets the generation of the `ID`s handed out for new slots.
+    pub fn generation_start(mut self, generation: G) -> Self {
+        self.generation_start = Some(generation);
+        self
+    }
+
+    /// Creates the arena, or returns an error if the settings contradict
+    /// each other.
+    pub fn build(self) -> Result<Arena<T, I, G>, BuildError> {
+        if self.max_len.is_some_and(|max_len| self.capacity > max_len) {
+            return Err(BuildError::CapacityAboveMaxLen);
+        }
+        let fresh_generation = match self.generation_start {
+            Some(generation) => generation.to_raw().ok_or(BuildError::GenerationOverflow)?,
+            None => G::FIRST,
+        };
+
+        Ok(Arena {
+            entries: storage(Vec::with_capacity(self.capacity)),
+            max_len: self.max_len,
+            fresh_generation,
+            ..Arena::default()
+        })
+    }
+}
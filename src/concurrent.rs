@@ -0,0 +1,194 @@
+use crate::{Arena, ID};
+use core::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    sync::atomic::{AtomicUsize, Ordering as AtomicOrdering},
+};
+use std::{
+    sync::{Mutex, MutexGuard, PoisonError},
+    thread,
+};
+
+/// An arena that can be inserted into from many threads at once.
+///
+/// An [`Arena`] is `Send` and `Sync` whenever `T` is, but needs `&mut`
+/// access, and so a single lock, to insert. A `ConcurrentArena` instead
+/// splits its entries over several shards behind their own locks and
+/// spreads inserts over them, so threads only contend when they happen
+/// to hit the same shard. A [`ConcurrentId`] records the shard of its entry.
+///
+/// Entries are only reachable through closures while shared, as they
+/// live behind a lock. With exclusive access, [`ConcurrentArena::get_mut`]
+/// and [`ConcurrentArena::into_shards`] bypass the locks.
+pub struct ConcurrentArena<T> {
+    shards: Box<[Mutex<Arena<T>>]>,
+    next_shard: AtomicUsize,
+}
+impl<T> ConcurrentArena<T> {
+    /// Creates an arena with one shard per available thread.
+    pub fn new() -> Self {
+        let shards = thread::available_parallelism().map_or(1, |n| n.get());
+        Self::with_shards(shards)
+    }
+    /// Creates an arena with `shards` shards.
+    ///
+    /// Panics if `shards` is zero.
+    pub fn with_shards(shards: usize) -> Self {
+        assert!(shards > 0, "a concurrent arena needs at least one shard");
+        Self {
+            shards: (0..shards).map(|_| Mutex::new(Arena::new())).collect(),
+            next_shard: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.shards.len()
+    }
+    /// Returns the number of entries in all shards.
+    ///
+    /// Other threads may insert or remove entries while the shards are
+    /// counted, so the result is only exact if nothing else accesses the arena.
+    pub fn len(&self) -> usize {
+        (0..self.shards.len()).map(|shard| self.lock(shard).len()).sum()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Inserts `t` into the first shard that is not locked, starting
+    /// from the next one in turn, or waits for that shard if all are locked.
+    pub fn insert(&self, t: T) -> ConcurrentId<T> {
+        let start = self.next_shard.fetch_add(1, AtomicOrdering::Relaxed) % self.shards.len();
+        let free_shard = (0..self.shards.len())
+            .map(|offset| (start + offset) % self.shards.len())
+            .find_map(|shard| Some((shard, self.shards[shard].try_lock().ok()?)));
+        let (shard, mut arena) = free_shard.unwrap_or_else(|| (start, self.lock(start)));
+
+        let id = arena.insert(t);
+        ConcurrentId { shard, id }
+    }
+    pub fn remove(&self, id: ConcurrentId<T>) -> Option<T> {
+        self.shard(id)?.remove(id.id)
+    }
+    pub fn contains(&self, id: ConcurrentId<T>) -> bool {
+        self.shard(id).is_some_and(|arena| arena.contains(id.id))
+    }
+
+    /// Calls `f` with the entry of `id` while its shard is locked.
+    ///
+    /// `f` must not access this arena, as it may deadlock on the same shard.
+    pub fn with<F, R>(&self, id: ConcurrentId<T>, f: F) -> Option<R>
+    where
+        F: FnOnce(&T) -> R,
+    {
+        self.shard(id)?.get(id.id).map(f)
+    }
+    /// Mutable version of [`ConcurrentArena::with`].
+    pub fn with_mut<F, R>(&self, id: ConcurrentId<T>, f: F) -> Option<R>
+    where
+        F: FnOnce(&mut T) -> R,
+    {
+        self.shard(id)?.get_mut(id.id).map(f)
+    }
+    /// Returns a clone of the entry of `id`.
+    pub fn get_cloned(&self, id: ConcurrentId<T>) -> Option<T>
+    where
+        T: Clone,
+    {
+        self.with(id, T::clone)
+    }
+    pub fn get_mut(&mut self, id: ConcurrentId<T>) -> Option<&mut T> {
+        let shard = self.shards.get_mut(id.shard)?;
+        shard.get_mut().unwrap_or_else(PoisonError::into_inner).get_mut(id.id)
+    }
+
+    /// Returns the shards as plain arenas, in shard order.
+    pub fn into_shards(self) -> Vec<Arena<T>> {
+        self.shards
+            .into_vec()
+            .into_iter()
+            .map(|shard| shard.into_inner().unwrap_or_else(PoisonError::into_inner))
+            .collect()
+    }
+
+    fn shard(&self, id: ConcurrentId<T>) -> Option<MutexGuard<'_, Arena<T>>> {
+        (id.shard < self.shards.len()).then(|| self.lock(id.shard))
+    }
+    fn lock(&self, shard: usize) -> MutexGuard<'_, Arena<T>> {
+        // Poisoning is ignored: a panic in a closure passed to `with_mut`
+        // can leave a value half-updated, but not the arena itself.
+        self.shards[shard].lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+impl<T> Default for ConcurrentArena<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl<T: Debug> Debug for ConcurrentArena<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        for shard in 0..self.shards.len() {
+            list.entry(&*self.lock(shard));
+        }
+        list.finish()
+    }
+}
+
+/// The ID of an entry in a [`ConcurrentArena<T>`].
+pub struct ConcurrentId<T> {
+    shard: usize,
+    id: ID<T>,
+}
+impl<T> ConcurrentId<T> {
+    pub fn shard(&self) -> usize {
+        self.shard
+    }
+    /// Returns the `ID` of the entry within the arena of its shard, as
+    /// returned by [`ConcurrentArena::into_shards`].
+    pub fn id(&self) -> ID<T> {
+        self.id
+    }
+}
+impl<T> Clone for ConcurrentId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for ConcurrentId<T> {}
+impl<T> PartialEq for ConcurrentId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.shard == other.shard && self.id == other.id
+    }
+}
+impl<T> Eq for ConcurrentId<T> {}
+impl<T> PartialOrd for ConcurrentId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<T> Ord for ConcurrentId<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.shard.cmp(&other.shard).then_with(|| self.id.cmp(&other.id))
+    }
+}
+impl<T> Hash for ConcurrentId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.shard.hash(state);
+        self.id.hash(state);
+    }
+}
+impl<T> Debug for ConcurrentId<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ConcurrentId")
+            .field("shard", &self.shard)
+            .field("id", &self.id)
+            .finish()
+    }
+}
+impl<T> Display for ConcurrentId<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}:{}", self.shard, self.id)
+    }
+}
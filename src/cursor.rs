@@ -0,0 +1,129 @@
+use crate::{ArenaGeneration, ArenaIndex, Entry, Generation, ID};
+
+/// A cursor over the entries of an arena, created by
+/// [`Arena::cursor_mut`](crate::Arena::cursor_mut).
+///
+/// The cursor points at one occupied entry at a time and gives mutable
+/// access to it, while all other entries can be read.
+pub struct CursorMut<'a, T, I = usize, G: ArenaGeneration = Generation> {
+    entries: &'a mut [Entry<T, I, G>],
+    current: Option<usize>,
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> CursorMut<'a, T, I, G> {
+    pub(crate) fn new(entries: &'a mut [Entry<T, I, G>]) -> Self {
+        let current = next_occupied(entries, 0);
+        Self { entries, current }
+    }
+
+    /// Returns the `ID` of the current entry, or `None` if the arena is empty.
+    pub fn id(&self) -> Option<ID<T, I, G>> {
+        let index = self.current?;
+        let Entry::Occupied(gen, _) = self.entries[index] else { unreachable!() };
+        Some(ID::new(index, gen))
+    }
+    pub fn current(&mut self) -> Option<(ID<T, I, G>, &mut T)> {
+        let id = self.id()?;
+        let (item, _) = self.split()?;
+        Some((id, item))
+    }
+    /// Returns the current value along with read access to all other entries.
+    pub fn split(&mut self) -> Option<(&mut T, Neighbors<'_, T, I, G>)> {
+        let index = self.current?;
+        let (before, rest) = self.entries.split_at_mut(index);
+        let (current, after) = rest.split_first_mut()?;
+        let Entry::Occupied(_, item) = current else { unreachable!() };
+
+        Some((item, Neighbors { before, after }))
+    }
+
+    /// Moves to the next occupied entry, returning false if there is none.
+    pub fn move_next(&mut self) -> bool {
+        let Some(index) = self.current else { return false };
+        match next_occupied(self.entries, index + 1) {
+            Some(next) => {
+                self.current = Some(next);
+                true
+            }
+            None => false,
+        }
+    }
+    /// Moves to the previous occupied entry, returning false if there is none.
+    pub fn move_prev(&mut self) -> bool {
+        let Some(index) = self.current else { return false };
+        match prev_occupied(self.entries, index) {
+            Some(prev) => {
+                self.current = Some(prev);
+                true
+            }
+            None => false,
+        }
+    }
+    pub fn peek_next(&self) -> Option<(ID<T, I, G>, &T)> {
+        let index = next_occupied(self.entries, self.current? + 1)?;
+        occupant(self.entries, index)
+    }
+    pub fn peek_prev(&self) -> Option<(ID<T, I, G>, &T)> {
+        let index = prev_occupied(self.entries, self.current?)?;
+        occupant(self.entries, index)
+    }
+
+    /// Returns the value behind `id`, or `None` if `id` is not valid
+    /// or refers to the current entry.
+    pub fn get(&self, id: ID<T, I, G>) -> Option<&T> {
+        if Some(id.index()) == self.current {
+            return None;
+        }
+        get(self.entries, 0, id)
+    }
+}
+
+/// Read access to every entry except the current one of a [`CursorMut`].
+pub struct Neighbors<'a, T, I = usize, G: ArenaGeneration = Generation> {
+    before: &'a [Entry<T, I, G>],
+    after: &'a [Entry<T, I, G>],
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> Neighbors<'a, T, I, G> {
+    /// Returns the value behind `id`, or `None` if `id` is not valid
+    /// or refers to the current entry.
+    pub fn get(&self, id: ID<T, I, G>) -> Option<&'a T> {
+        let current = self.before.len();
+        match id.index() {
+            index if index < current => get(self.before, 0, id),
+            index if index > current => get(self.after, current + 1, id),
+            _ => None,
+        }
+    }
+    pub fn prev(&self) -> Option<(ID<T, I, G>, &'a T)> {
+        let index = prev_occupied(self.before, self.before.len())?;
+        occupant(self.before, index)
+    }
+    pub fn next(&self) -> Option<(ID<T, I, G>, &'a T)> {
+        let offset = self.before.len() + 1;
+        let index = next_occupied(self.after, 0)?;
+        let (id, item) = occupant(self.after, index)?;
+        Some((ID::new(offset + id.index(), id.generation), item))
+    }
+}
+
+fn next_occupied<T, I, G: ArenaGeneration>(entries: &[Entry<T, I, G>], from: usize) -> Option<usize> {
+    let offset = entries[from..]
+        .iter()
+        .position(|entry| matches!(entry, Entry::Occupied(..)))?;
+    Some(from + offset)
+}
+fn prev_occupied<T, I, G: ArenaGeneration>(entries: &[Entry<T, I, G>], before: usize) -> Option<usize> {
+    entries[..before]
+        .iter()
+        .rposition(|entry| matches!(entry, Entry::Occupied(..)))
+}
+fn occupant<T, I: ArenaIndex, G: ArenaGeneration>(
+    entries: &[Entry<T, I, G>],
+    index: usize,
+) -> Option<(ID<T, I, G>, &T)> {
+    let Entry::Occupied(gen, item) = &entries[index] else { return None };
+    Some((ID::new(index, *gen), item))
+}
+fn get<T, I: ArenaIndex, G: ArenaGeneration>(entries: &[Entry<T, I, G>], offset: usize, id: ID<T, I, G>) -> Option<&T> {
+    let Entry::Occupied(gen, item) = entries.get(id.index() - offset)? else { return None };
+    (*gen == id.generation).then_some(item)
+}
@@ -0,0 +1,133 @@
+use crate::{ArenaGeneration, Generation};
+use core::{error::Error, fmt::Display};
+
+/// The error returned by [`Arena::insert_at`](crate::Arena::insert_at).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum InsertAtError {
+    Occupied,
+    Retired,
+    Reserved,
+    Full,
+    Null,
+}
+impl Display for InsertAtError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Occupied => write!(f, "slot is already occupied"),
+            Self::Retired => write!(f, "slot has been retired"),
+            Self::Reserved => write!(f, "slot is reserved"),
+            Self::Full => write!(f, "arena is full"),
+            Self::Null => write!(f, "cannot insert at the null ID"),
+        }
+    }
+}
+impl Error for InsertAtError {}
+
+/// The error returned by [`Arena::try_get`](crate::Arena::try_get),
+/// describing why an `ID` could not be resolved.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum AccessError<G: ArenaGeneration = Generation> {
+    OutOfBounds,
+    /// The slot is free, reserved or retired.
+    Vacant,
+    /// The slot is occupied by a later generation than the one of the `ID`.
+    StaleGeneration { expected: G, found: G },
+}
+impl<G: ArenaGeneration> Display for AccessError<G> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::OutOfBounds => write!(f, "index is out of bounds"),
+            Self::Vacant => write!(f, "slot is vacant"),
+            Self::StaleGeneration { expected, found } => {
+                write!(f, "generation mismatch: expected {expected}, found {found}")
+            }
+        }
+    }
+}
+impl<G: ArenaGeneration> Error for AccessError<G> {}
+
+/// The error returned by [`Arena::from_entries`](crate::Arena::from_entries)
+/// when the given slots do not form a consistent arena.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum FromEntriesError {
+    /// There are more slots than the index type can address.
+    TooManyEntries,
+    /// A generation is too large to be stored.
+    GenerationOverflow,
+    LinkOutOfBounds,
+    /// A free slot links to a slot that is not free.
+    LinkToNonFree,
+    /// A free slot is linked to by more than one other slot.
+    LinkedTwice,
+    /// The free slots form more than one chain.
+    NotSingleChain,
+    Cycle,
+    /// There are more occupied and reserved slots than the maximum length.
+    AboveMaxLen,
+}
+impl Display for FromEntriesError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::TooManyEntries => write!(f, "too many slots for the index type"),
+            Self::GenerationOverflow => write!(f, "generation is too large"),
+            Self::LinkOutOfBounds => write!(f, "free list links out of bounds"),
+            Self::LinkToNonFree => write!(f, "free list links to a slot that is not free"),
+            Self::LinkedTwice => write!(f, "free slot is linked more than once"),
+            Self::NotSingleChain => write!(f, "free list is not a single chain"),
+            Self::Cycle => write!(f, "free list contains a cycle"),
+            Self::AboveMaxLen => write!(f, "more entries than the maximum length"),
+        }
+    }
+}
+impl Error for FromEntriesError {}
+
+/// The error returned by [`Arena::reset_generations`](crate::Arena::reset_generations)
+/// if the arena still has live or reserved entries.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct NotEmptyError;
+impl Display for NotEmptyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "arena is not empty")
+    }
+}
+impl Error for NotEmptyError {}
+
+/// The error returned by [`ArenaBuilder::build`](crate::ArenaBuilder::build)
+/// for contradicting settings.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BuildError {
+    /// The capacity is larger than the maximum length.
+    CapacityAboveMaxLen,
+    /// The start generation is too large to be stored.
+    GenerationOverflow,
+}
+impl Display for BuildError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::CapacityAboveMaxLen => write!(f, "capacity exceeds the maximum length"),
+            Self::GenerationOverflow => write!(f, "start generation is too large"),
+        }
+    }
+}
+impl Error for BuildError {}
+
+/// The error returned by [`Arena::move_to`](crate::Arena::move_to).
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum MoveError {
+    /// The `ID` to move is not valid.
+    InvalidId,
+    /// The target slot is occupied, reserved or retired.
+    NotFree,
+    /// The target slot is past the end of the arena.
+    OutOfBounds,
+}
+impl Display for MoveError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::InvalidId => write!(f, "ID is not valid"),
+            Self::NotFree => write!(f, "target slot is not free"),
+            Self::OutOfBounds => write!(f, "target slot is out of bounds"),
+        }
+    }
+}
+impl Error for MoveError {}
@@ -0,0 +1,233 @@
+use alloc::vec::Vec;
+use core::{
+    cmp::Ordering,
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    iter::{Enumerate, FusedIterator},
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+/// An append-only arena without generation tracking.
+///
+/// Entries can never be removed, so a [`FastId`] is just an index and
+/// accessing an entry only costs a bounds check. Use an [`Arena`](crate::Arena)
+/// if entries need to be removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FastArena<T> {
+    items: Vec<T>,
+}
+impl<T> FastArena<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            items: Vec::with_capacity(capacity),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+    pub fn capacity(&self) -> usize {
+        self.items.capacity()
+    }
+    pub fn reserve(&mut self, additional: usize) {
+        self.items.reserve(additional);
+    }
+
+    pub fn insert(&mut self, t: T) -> FastId<T> {
+        let id = FastId::new(self.items.len());
+        self.items.push(t);
+        id
+    }
+    pub fn peek_next_id(&self) -> FastId<T> {
+        FastId::new(self.items.len())
+    }
+
+    pub fn get(&self, id: FastId<T>) -> Option<&T> {
+        self.items.get(id.index)
+    }
+    pub fn get_mut(&mut self, id: FastId<T>) -> Option<&mut T> {
+        self.items.get_mut(id.index)
+    }
+    pub fn contains(&self, id: FastId<T>) -> bool {
+        id.index < self.items.len()
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            items: self.items.iter().enumerate(),
+        }
+    }
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            items: self.items.iter_mut().enumerate(),
+        }
+    }
+    pub fn values(&self) -> slice::Iter<'_, T> {
+        self.items.iter()
+    }
+    pub fn values_mut(&mut self) -> slice::IterMut<'_, T> {
+        self.items.iter_mut()
+    }
+    pub fn into_vec(self) -> Vec<T> {
+        self.items
+    }
+}
+impl<T> Default for FastArena<T> {
+    fn default() -> Self {
+        Self { items: Vec::new() }
+    }
+}
+impl<T> From<Vec<T>> for FastArena<T> {
+    fn from(items: Vec<T>) -> Self {
+        Self { items }
+    }
+}
+impl<T> Index<FastId<T>> for FastArena<T> {
+    type Output = T;
+    fn index(&self, index: FastId<T>) -> &Self::Output {
+        &self.items[index.index]
+    }
+}
+impl<T> IndexMut<FastId<T>> for FastArena<T> {
+    fn index_mut(&mut self, index: FastId<T>) -> &mut Self::Output {
+        &mut self.items[index.index]
+    }
+}
+impl<T> FromIterator<T> for FastArena<T> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        Self {
+            items: iter.into_iter().collect(),
+        }
+    }
+}
+impl<T> Extend<T> for FastArena<T> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        self.items.extend(iter);
+    }
+}
+impl<T> IntoIterator for FastArena<T> {
+    type IntoIter = alloc::vec::IntoIter<T>;
+    type Item = T;
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+impl<'a, T> IntoIterator for &'a FastArena<T> {
+    type IntoIter = Iter<'a, T>;
+    type Item = (FastId<T>, &'a T);
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, T> IntoIterator for &'a mut FastArena<T> {
+    type IntoIter = IterMut<'a, T>;
+    type Item = (FastId<T>, &'a mut T);
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+/// The ID of an entry in a [`FastArena<T>`].
+///
+/// Unlike an [`ID`](crate::ID) it has no generation, so it can't be mixed
+/// up with the IDs of a generational arena.
+pub struct FastId<T> {
+    index: usize,
+    marker: PhantomData<fn() -> T>,
+}
+impl<T> FastId<T> {
+    fn new(index: usize) -> Self {
+        Self {
+            index,
+            marker: PhantomData,
+        }
+    }
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+impl<T> Clone for FastId<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T> Copy for FastId<T> {}
+impl<T> PartialEq for FastId<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+impl<T> Eq for FastId<T> {}
+impl<T> PartialOrd for FastId<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<T> Ord for FastId<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index.cmp(&other.index)
+    }
+}
+impl<T> Hash for FastId<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+impl<T> Debug for FastId<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("FastId").field(&self.index).finish()
+    }
+}
+impl<T> Display for FastId<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.index)
+    }
+}
+
+pub struct Iter<'a, T> {
+    items: Enumerate<slice::Iter<'a, T>>,
+}
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (FastId<T>, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next().map(|(index, t)| (FastId::new(index), t))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.items.next_back().map(|(index, t)| (FastId::new(index), t))
+    }
+}
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+impl<'a, T> FusedIterator for Iter<'a, T> {}
+
+pub struct IterMut<'a, T> {
+    items: Enumerate<slice::IterMut<'a, T>>,
+}
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = (FastId<T>, &'a mut T);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next().map(|(index, t)| (FastId::new(index), t))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+}
+impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.items.next_back().map(|(index, t)| (FastId::new(index), t))
+    }
+}
+impl<'a, T> ExactSizeIterator for IterMut<'a, T> {}
+impl<'a, T> FusedIterator for IterMut<'a, T> {}
@@ -0,0 +1,57 @@
+use core::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    num::{NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, ParseIntError},
+    str::FromStr,
+};
+
+/// An unsigned integer usable as the generation counter of an arena.
+///
+/// Smaller generations shrink every `ID` and free slot, but a slot's
+/// generation runs out after fewer reuses: with a `u8` generation a
+/// slot can only be reused 254 times. Larger generations cost
+/// memory but practically never run out, which suits long-running
+/// arenas with a lot of churn.
+///
+/// Generations are stored offset by one in a non-zero integer, so that
+/// `Option<ID>` is no larger than `ID`.
+pub trait ArenaGeneration:
+    Copy + Eq + Ord + Hash + Debug + Display + FromStr<Err = ParseIntError>
+{
+    type Raw: Copy + Eq + Ord + Hash + Debug;
+    const FIRST: Self::Raw;
+
+    fn from_raw(raw: Self::Raw) -> Self;
+    fn to_raw(self) -> Option<Self::Raw>;
+    fn next(raw: Self::Raw) -> Option<Self::Raw>;
+    fn to_u64(self) -> u64;
+    fn try_from_u64(value: u64) -> Option<Self>;
+}
+
+macro_rules! impl_arena_generation {
+    ($($ty:ty => $raw:ty),*) => {
+        $(
+            impl ArenaGeneration for $ty {
+                type Raw = $raw;
+                const FIRST: Self::Raw = <$raw>::MIN;
+
+                fn from_raw(raw: Self::Raw) -> Self {
+                    raw.get() - 1
+                }
+                fn to_raw(self) -> Option<Self::Raw> {
+                    self.checked_add(1).and_then(<$raw>::new)
+                }
+                fn next(raw: Self::Raw) -> Option<Self::Raw> {
+                    raw.checked_add(1)
+                }
+                fn to_u64(self) -> u64 {
+                    self.into()
+                }
+                fn try_from_u64(value: u64) -> Option<Self> {
+                    value.try_into().ok()
+                }
+            }
+        )*
+    };
+}
+impl_arena_generation!(u8 => NonZeroU8, u16 => NonZeroU16, u32 => NonZeroU32, u64 => NonZeroU64);
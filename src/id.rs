@@ -0,0 +1,235 @@
+use crate::{ArenaGeneration, ArenaIndex, Generation};
+use core::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{Debug, Display},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    num::ParseIntError,
+    str::FromStr,
+};
+
+/// The default marker of an [`ID`] that is not tied to any arena.
+///
+/// An `Arena<T>` only accepts `ID<T>`, an untyped `ID` has to be
+/// converted with [`ID::rebrand`] first.
+pub enum Untyped {}
+
+/// With the `serde` feature, an `ID` serializes to its `Display` form in
+/// human-readable formats and to the packed integer of [`ID::to_bits`] in
+/// binary formats, which fails for `ID`s that `to_bits` cannot pack.
+pub struct ID<M = Untyped, I = usize, G: ArenaGeneration = Generation> {
+    pub(crate) index: I,
+    pub(crate) generation: G::Raw,
+    marker: PhantomData<fn() -> M>,
+}
+impl<M, I: ArenaIndex, G: ArenaGeneration> ID<M, I, G> {
+    /// An `ID` that no arena ever hands out, so looking it up always fails.
+    pub const NULL: Self = Self {
+        index: I::MAX,
+        generation: G::FIRST,
+        marker: PhantomData,
+    };
+
+    pub(crate) fn new(index: usize, generation: G::Raw) -> Self {
+        let index = I::from_usize(index);
+        Self {
+            index,
+            generation,
+            marker: PhantomData,
+        }
+    }
+
+    /// Builds an `ID` from its index and generation, e.g. after getting
+    /// them across an FFI boundary.
+    ///
+    /// The result is only valid if an arena actually handed out an `ID`
+    /// with these parts, which the arena checks on every access.
+    ///
+    /// Panics if `index` does not fit `I` or `generation` is the largest
+    /// value of `G`, neither of which any arena ever hands out.
+    pub fn from_parts(index: usize, generation: G) -> Self {
+        let generation = generation.to_raw().expect("ID generation out of range");
+        Self::new(index, generation)
+    }
+    pub fn index(&self) -> usize {
+        self.index.to_usize()
+    }
+    pub fn generation(&self) -> G {
+        G::from_raw(self.generation)
+    }
+    /// Returns whether this `ID` has the index of [`ID::NULL`].
+    ///
+    /// The generation is not compared: every `ID` with the index `I::MAX`
+    /// counts as null, since no arena ever hands out that index, and
+    /// [`ID::to_bits`] and serialization write all of them like `ID::NULL`.
+    pub fn is_null(&self) -> bool {
+        self.index == I::MAX
+    }
+
+    /// Reinterprets this `ID` as belonging to an arena of `N`.
+    ///
+    /// Nothing ties the resulting `ID` to an actual value of `N`,
+    /// so only use this when the `ID` was really handed out by such an arena.
+    pub fn rebrand<N>(self) -> ID<N, I, G> {
+        ID {
+            index: self.index,
+            generation: self.generation,
+            marker: PhantomData,
+        }
+    }
+}
+impl<M, I: ArenaIndex> ID<M, I, Generation> {
+    /// Packs this `ID` into a `u64`, with the index in the low 32 bits
+    /// and the generation in the high 32 bits.
+    ///
+    /// Null `ID`s, see [`ID::is_null`], pack to `u32::MAX`, i.e. an index
+    /// of all ones and generation 0, whatever the index type.
+    ///
+    /// Panics if the index does not fit below `u32::MAX`, which can only
+    /// happen for arenas with more than `u32::MAX - 1` slots.
+    pub fn to_bits(self) -> u64 {
+        self.try_to_bits().expect("ID index does not fit in 32 bits")
+    }
+    /// Unpacks an `ID` previously packed with [`ID::to_bits`].
+    ///
+    /// Panics if the index does not fit `I`, the generation is `u32::MAX`,
+    /// or the index is `u32::MAX` with a generation other than 0, none
+    /// of which `to_bits` ever produces.
+    pub fn from_bits(bits: u64) -> Self {
+        Self::try_from_bits(bits).expect("ID index or generation out of range")
+    }
+}
+impl<M, I: ArenaIndex, G: ArenaGeneration> ID<M, I, G> {
+    /// Packs this `ID` like [`ID::to_bits`], or returns `None` if the index
+    /// or generation does not fit in 32 bits.
+    pub(crate) fn try_to_bits(self) -> Option<u64> {
+        if self.is_null() {
+            return Some(NULL_BITS);
+        }
+        let index = u32::try_from(self.index()).ok().filter(|&index| index != u32::MAX)?;
+        let generation = u32::try_from(self.generation().to_u64()).ok()?;
+        Some((generation as u64) << 32 | index as u64)
+    }
+    pub(crate) fn try_from_bits(bits: u64) -> Option<Self> {
+        if bits == NULL_BITS {
+            return Some(Self::NULL);
+        }
+        let index = Some(bits as u32).filter(|&index| index != u32::MAX)?;
+        let index = I::try_from_usize(index as usize)?;
+        let generation = G::try_from_u64(bits >> 32)?.to_raw()?;
+        Some(Self {
+            index,
+            generation,
+            marker: PhantomData,
+        })
+    }
+}
+/// The packed form of [`ID::NULL`], whose index does not fit in 32 bits
+/// for most index types.
+const NULL_BITS: u64 = u32::MAX as u64;
+
+impl<M, I: ArenaIndex, G: ArenaGeneration> Clone for ID<M, I, G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<M, I: ArenaIndex, G: ArenaGeneration> Copy for ID<M, I, G> {}
+impl<M, I: ArenaIndex, G: ArenaGeneration> PartialEq for ID<M, I, G> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index && self.generation == other.generation
+    }
+}
+impl<M, I: ArenaIndex, G: ArenaGeneration> Eq for ID<M, I, G> {}
+/// IDs are ordered by index, then by generation.
+///
+/// This order only exists to make IDs usable as sorted keys. It does not
+/// say anything about when the IDs were handed out, in particular a higher
+/// generation in one slot is not more recent than a lower one in another.
+impl<M, I: ArenaIndex, G: ArenaGeneration> Ord for ID<M, I, G> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.index
+            .cmp(&other.index)
+            .then_with(|| self.generation.cmp(&other.generation))
+    }
+}
+impl<M, I: ArenaIndex, G: ArenaGeneration> PartialOrd for ID<M, I, G> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl<M, I: ArenaIndex, G: ArenaGeneration> Hash for ID<M, I, G> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+        self.generation.hash(state);
+    }
+}
+impl<M, I: ArenaIndex, G: ArenaGeneration> Debug for ID<M, I, G> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ID")
+            .field("index", &self.index)
+            .field("generation", &self.generation())
+            .finish()
+    }
+}
+impl<M, I: ArenaIndex, G: ArenaGeneration> Display for ID<M, I, G> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.generation == G::FIRST {
+            write!(f, "{}", self.index)
+        } else {
+            write!(f, "({}-{})", self.index, self.generation())
+        }
+    }
+}
+impl<M, I: ArenaIndex, G: ArenaGeneration> FromStr for ID<M, I, G> {
+    type Err = ParseIDError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (index, generation) = match s.strip_prefix('(') {
+            Some(rest) => {
+                let rest = rest.strip_suffix(')').ok_or(ParseIDError::MissingParenthesis)?;
+                let (index, generation) = rest.split_once('-').ok_or(ParseIDError::MissingSeparator)?;
+                let generation: G = generation.parse().map_err(ParseIDError::InvalidGeneration)?;
+                (index, generation.to_raw().ok_or(ParseIDError::OutOfRange)?)
+            }
+            None if s.contains('-') || s.ends_with(')') => return Err(ParseIDError::MissingParenthesis),
+            None => (s, G::FIRST),
+        };
+
+        let index: usize = index.parse().map_err(ParseIDError::InvalidIndex)?;
+        let index = I::try_from_usize(index).ok_or(ParseIDError::OutOfRange)?;
+        Ok(Self {
+            index,
+            generation,
+            marker: PhantomData,
+        })
+    }
+}
+
+/// The error returned when parsing an [`ID`] from its `Display` form fails.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseIDError {
+    MissingParenthesis,
+    MissingSeparator,
+    InvalidIndex(ParseIntError),
+    InvalidGeneration(ParseIntError),
+    OutOfRange,
+}
+impl Display for ParseIDError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::MissingParenthesis => write!(f, "an ID with a generation must be enclosed in parentheses"),
+            Self::MissingSeparator => write!(f, "expected `-` between index and generation"),
+            Self::InvalidIndex(e) => write!(f, "invalid index: {e}"),
+            Self::InvalidGeneration(e) => write!(f, "invalid generation: {e}"),
+            Self::OutOfRange => write!(f, "index or generation out of range"),
+        }
+    }
+}
+impl Error for ParseIDError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::InvalidIndex(e) | Self::InvalidGeneration(e) => Some(e),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use core::{
+    any::type_name,
+    fmt::{Debug, Display},
+    hash::Hash,
+};
+
+pub trait ArenaIndex: Copy + Eq + Ord + Hash + Debug + Display {
+    /// The largest value of this type, which is reserved for [`ID::NULL`](crate::ID::NULL).
+    const MAX: Self;
+
+    fn try_from_usize(index: usize) -> Option<Self>;
+    fn to_usize(self) -> usize;
+
+    /// Converts a slot index into this index type.
+    ///
+    /// Panics if `index` does not fit, so an arena can never hand out
+    /// an `ID` whose index silently wrapped around.
+    fn from_usize(index: usize) -> Self {
+        match Self::try_from_usize(index) {
+            Some(index) => index,
+            None => panic!("arena index {index} exceeds {}::MAX", type_name::<Self>()),
+        }
+    }
+}
+
+macro_rules! impl_arena_index {
+    ($($ty:ty),*) => {
+        $(
+            impl ArenaIndex for $ty {
+                const MAX: Self = <$ty>::MAX;
+
+                fn try_from_usize(index: usize) -> Option<Self> {
+                    Self::try_from(index).ok()
+                }
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+impl_arena_index!(u8, u16, u32, u64, usize);
@@ -0,0 +1,96 @@
+use crate::{Arena, Iter, ID};
+use core::{borrow::Borrow, fmt::Debug, hash::Hash};
+use std::collections::HashMap;
+
+/// An [`Arena`] that can also find its entries by a key derived from
+/// each value, e.g. to intern values.
+///
+/// The key of a value is computed with the function passed to
+/// [`IndexedArena::new`] whenever the value is inserted or removed, and
+/// no two entries can have the same key. The key of a value must not
+/// change while it is in the arena, so mutating values through
+/// [`IndexedArena::get_mut`] must leave their key intact.
+pub struct IndexedArena<T, K, F = fn(&T) -> K> {
+    arena: Arena<T>,
+    keys: HashMap<K, ID<T>>,
+    key_of: F,
+}
+impl<T, K: Eq + Hash, F: Fn(&T) -> K> IndexedArena<T, K, F> {
+    pub fn new(key_of: F) -> Self {
+        Self {
+            arena: Arena::new(),
+            keys: HashMap::new(),
+            key_of,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.arena.len()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.arena.is_empty()
+    }
+
+    /// Inserts `t`, or gives it back if an entry with the same key exists.
+    pub fn insert(&mut self, t: T) -> Result<ID<T>, T> {
+        let key = (self.key_of)(&t);
+        if self.keys.contains_key(&key) {
+            return Err(t);
+        }
+        let id = self.arena.insert(t);
+        self.keys.insert(key, id);
+        Ok(id)
+    }
+    /// Returns the `ID` of the entry with the key of `t`, inserting `t`
+    /// if there is none.
+    pub fn intern(&mut self, t: T) -> ID<T> {
+        let key = (self.key_of)(&t);
+        *self.keys.entry(key).or_insert_with(|| self.arena.insert(t))
+    }
+    pub fn remove(&mut self, id: ID<T>) -> Option<T> {
+        let t = self.arena.remove(id)?;
+        self.keys.remove(&(self.key_of)(&t));
+        Some(t)
+    }
+
+    pub fn get(&self, id: ID<T>) -> Option<&T> {
+        self.arena.get(id)
+    }
+    /// Returns the value behind `id`, which must keep its key.
+    pub fn get_mut(&mut self, id: ID<T>) -> Option<&mut T> {
+        self.arena.get_mut(id)
+    }
+    pub fn contains(&self, id: ID<T>) -> bool {
+        self.arena.contains(id)
+    }
+    /// Returns the `ID` of the entry with `key`.
+    pub fn get_by_key<Q>(&self, key: &Q) -> Option<ID<T>>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.keys.get(key).copied()
+    }
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.keys.contains_key(key)
+    }
+
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.arena.iter()
+    }
+    pub fn arena(&self) -> &Arena<T> {
+        &self.arena
+    }
+    pub fn into_arena(self) -> Arena<T> {
+        self.arena
+    }
+}
+impl<T: Debug, K, F> Debug for IndexedArena<T, K, F> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("IndexedArena").field(&self.arena).finish()
+    }
+}
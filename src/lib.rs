@@ -1,26 +1,257 @@
-use std::{
-    fmt::{Debug, Display},
-    hash::Hash,
-    mem::replace,
-    ops::{Index, IndexMut},
+#![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "allocator_api", feature(allocator_api))]
+
+extern crate alloc;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use allocator::{storage, Storage, StorageIntoIter};
+use core::{
+    fmt::Debug,
+    hash::{Hash, Hasher},
+    iter::FusedIterator,
+    mem::take,
+    ops::{Bound, Index, IndexMut, Range, RangeBounds},
 };
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+
+mod allocator;
+mod builder;
+#[cfg(feature = "std")]
+pub mod concurrent;
+mod cursor;
+mod error;
+pub mod fast;
+mod generation;
+mod id;
+mod index;
+#[cfg(feature = "std")]
+pub mod indexed;
+#[cfg(feature = "rayon")]
+mod rayon_impl;
+pub mod secondary;
+#[cfg(feature = "serde")]
+mod serde_impl;
+
+pub use allocator::{Allocator, Global};
+pub use builder::ArenaBuilder;
+#[cfg(feature = "std")]
+pub use concurrent::{ConcurrentArena, ConcurrentId};
+pub use cursor::{CursorMut, Neighbors};
+pub use error::{AccessError, BuildError, FromEntriesError, InsertAtError, MoveError, NotEmptyError};
+pub use fast::{FastArena, FastId};
+pub use generation::ArenaGeneration;
+pub use id::{ParseIDError, Untyped, ID};
+pub use index::ArenaIndex;
+#[cfg(feature = "std")]
+pub use indexed::IndexedArena;
+pub use secondary::SecondaryMap;
 
 pub type Generation = u32;
+/// A removed value and the old and new `ID` of the entry moved into its
+/// slot, as returned by [`Arena::remove_compacting`].
+pub type CompactingRemoval<T, I = usize, G = Generation> = (T, Option<(ID<T, I, G>, ID<T, I, G>)>);
+/// A map from old to new `ID`s, as returned by operations that move entries.
+#[cfg(feature = "std")]
+pub type IdRemap<T, I = usize, G = Generation> = HashMap<ID<T, I, G>, ID<T, I, G>>;
+/// The matching entries, the remaining entries and the map from old to
+/// new `ID`s, as returned by [`Arena::partition`].
+#[cfg(feature = "std")]
+pub type Partition<T, I = usize, G = Generation> = (Arena<T, I, G>, Arena<T, I, G>, IdRemap<T, I, G>);
 
-#[derive(Clone, Debug)]
-pub struct Arena<T> {
-    entries: Vec<Entry<T>>,
-    free_list_head: Option<usize>,
+pub struct Arena<T, I = usize, G: ArenaGeneration = Generation, A: Allocator = Global> {
+    entries: Storage<Entry<T, I, G>, A>,
+    free_list_head: Option<I>,
     length: usize,
+    retired: usize,
+    reserved: usize,
+    max_len: Option<usize>,
+    fresh_generation: G::Raw,
+    on_grow: Option<fn(usize, usize)>,
 }
 impl<T> Arena<T> {
     pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            entries: storage(Vec::with_capacity(capacity)),
+            ..Self::default()
+        }
+    }
+
+    /// Creates an arena that holds at most `max_len` live entries.
+    ///
+    /// Inserting into a full arena with [`Arena::insert`] panics,
+    /// use [`Arena::try_insert`] to get the value back instead.
+    pub fn with_max_len(max_len: usize) -> Self {
         Self {
-            entries: Vec::new(),
+            max_len: Some(max_len),
+            ..Self::default()
+        }
+    }
+    /// Creates an arena whose new slots start at generation `start`
+    /// instead of zero, like [`ArenaBuilder::generation_start`].
+    ///
+    /// Panics if `start` is `Generation::MAX`, which can't be stored.
+    pub fn with_generation_start(start: Generation) -> Self {
+        Self {
+            fresh_generation: start.to_raw().expect("generation start out of range"),
+            ..Self::default()
+        }
+    }
+    /// Creates an arena that holds element `i` of `v` at index `i`
+    /// with the first generation, and has no free slots.
+    pub fn from_vec(v: Vec<T>) -> Self {
+        let length = v.len();
+        let entries: Vec<_> = v
+            .into_iter()
+            .map(|t| Entry::Occupied(Generation::FIRST, t))
+            .collect();
+        Self {
+            entries: storage(entries),
+            length,
+            ..Self::default()
+        }
+    }
+}
+#[cfg(feature = "allocator_api")]
+impl<T, A: Allocator> Arena<T, usize, Generation, A> {
+    /// Creates an arena whose slots are allocated with `alloc`.
+    pub fn new_in(alloc: A) -> Self {
+        Self::with_capacity_in(0, alloc)
+    }
+    pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
+        Self {
+            entries: Vec::with_capacity_in(capacity, alloc),
             free_list_head: None,
             length: 0,
+            retired: 0,
+            reserved: 0,
+            max_len: None,
+            fresh_generation: Generation::FIRST,
+            on_grow: None,
         }
     }
+}
+impl<T, I: ArenaIndex, G: ArenaGeneration> Arena<T, I, G> {
+    /// Returns a builder to configure a new arena.
+    pub fn builder() -> ArenaBuilder<T, I, G> {
+        ArenaBuilder::new()
+    }
+    /// Rebuilds an arena from the full, ordered list of its slots.
+    ///
+    /// The free slots must link into a single chain, which becomes the
+    /// free list of the arena. Slots appended later start at the first
+    /// generation, so this is only faithful for arenas that never dropped
+    /// slots from their end, see [`Arena::from_entries_with`].
+    pub fn from_entries<It>(entries: It) -> Result<Self, FromEntriesError>
+    where
+        It: IntoIterator<Item = RawEntry<T, G>>,
+    {
+        Self::from_entries_with(entries, G::from_raw(G::FIRST), None)
+    }
+    /// Like [`Arena::from_entries`], but also restores the generation that
+    /// new slots start at, as returned by [`Arena::fresh_generation`], and
+    /// the maximum length.
+    ///
+    /// An arena that dropped slots from its end, e.g. in
+    /// [`Arena::shrink_to_fit`], must be rebuilt with its fresh generation,
+    /// or stale `ID`s of the dropped slots would resolve again.
+    pub fn from_entries_with<It>(
+        entries: It,
+        fresh_generation: G,
+        max_len: Option<usize>,
+    ) -> Result<Self, FromEntriesError>
+    where
+        It: IntoIterator<Item = RawEntry<T, G>>,
+    {
+        let entries = entries
+            .into_iter()
+            .map(|entry| {
+                Ok(match entry {
+                    RawEntry::Occupied { generation, value } => {
+                        let gen = generation.to_raw().ok_or(FromEntriesError::GenerationOverflow)?;
+                        Entry::Occupied(gen, value)
+                    }
+                    RawEntry::Free {
+                        next_generation,
+                        next_free,
+                    } => Entry::Free {
+                        next_generation: next_generation
+                            .to_raw()
+                            .ok_or(FromEntriesError::GenerationOverflow)?,
+                        next_free: next_free
+                            .map(|next| I::try_from_usize(next).ok_or(FromEntriesError::LinkOutOfBounds))
+                            .transpose()?,
+                    },
+                    RawEntry::Reserved { generation } => {
+                        let gen = generation.to_raw().ok_or(FromEntriesError::GenerationOverflow)?;
+                        Entry::Reserved(gen)
+                    }
+                    RawEntry::Retired => Entry::Retired,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let fresh_generation = fresh_generation.to_raw().ok_or(FromEntriesError::GenerationOverflow)?;
+
+        Self::from_raw_entries(entries, max_len, fresh_generation)
+    }
+    pub(crate) fn from_raw_entries(
+        entries: Vec<Entry<T, I, G>>,
+        max_len: Option<usize>,
+        fresh_generation: G::Raw,
+    ) -> Result<Self, FromEntriesError> {
+        if entries.len() > I::MAX.to_usize() {
+            return Err(FromEntriesError::TooManyEntries);
+        }
+
+        let free_list_head = find_free_list_head(&entries)?;
+        let length = entries
+            .iter()
+            .filter(|entry| matches!(entry, Entry::Occupied(..)))
+            .count();
+        let retired = entries
+            .iter()
+            .filter(|entry| matches!(entry, Entry::Retired))
+            .count();
+        let reserved = entries
+            .iter()
+            .filter(|entry| matches!(entry, Entry::Reserved(..)))
+            .count();
+        if max_len.is_some_and(|max_len| length + reserved > max_len) {
+            return Err(FromEntriesError::AboveMaxLen);
+        }
+
+        Ok(Self {
+            entries: storage(entries),
+            free_list_head,
+            length,
+            retired,
+            reserved,
+            max_len,
+            fresh_generation,
+            on_grow: None,
+        })
+    }
+}
+impl<T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> Arena<T, I, G, A> {
+    /// Converts an arena without holes back into a `Vec` indexed by slot.
+    ///
+    /// Generations are discarded. If the arena has any free or retired
+    /// slots it is handed back unchanged.
+    pub fn into_vec(self) -> Result<Vec<T>, Self> {
+        if self.length != self.entries.len() {
+            return Err(self);
+        }
+
+        Ok(self.into_values().collect())
+    }
 
     pub fn len(&self) -> usize {
         self.length
@@ -28,54 +259,1061 @@ impl<T> Arena<T> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+    pub fn max_len(&self) -> Option<usize> {
+        self.max_len
+    }
+    /// Returns the generation that slots appended to the arena from now
+    /// on start at.
+    ///
+    /// It rises above the first generation once slots are dropped from the
+    /// end of the arena, so that stale `ID`s of those slots stay stale.
+    pub fn fresh_generation(&self) -> G {
+        G::from_raw(self.fresh_generation)
+    }
+    pub fn is_full(&self) -> bool {
+        self.max_len.is_some_and(|max_len| self.length + self.reserved >= max_len)
+    }
+    /// Returns the number of slots that were permanently taken out of use
+    /// because their generation ran out.
+    pub fn retired_count(&self) -> usize {
+        self.retired
+    }
+    /// Returns the number of slots handed out by [`Arena::reserve_id`]
+    /// that have not been filled yet.
+    pub fn reserved_len(&self) -> usize {
+        self.reserved
+    }
+    pub fn capacity(&self) -> usize {
+        self.entries.capacity()
+    }
+    /// Returns how many more entries can be inserted before the arena
+    /// has to reallocate: the free slots plus the unused capacity.
+    pub fn capacity_remaining(&self) -> usize {
+        self.free_len() + (self.entries.capacity() - self.entries.len())
+    }
+    /// Sets a function to call with the old and new capacity whenever an
+    /// insert makes the arena reallocate, or removes it with `None`.
+    ///
+    /// Bulk inserts like `extend` and [`Arena::insert_batch`] trigger it
+    /// when they allocate up front, but explicit calls like
+    /// [`Arena::reserve`] do not.
+    pub fn set_on_grow(&mut self, on_grow: Option<fn(usize, usize)>) {
+        self.on_grow = on_grow;
+    }
+    pub fn free_len(&self) -> usize {
+        self.entries.len() - self.length - self.retired - self.reserved
+    }
+    /// Returns a summary of how the arena's slots are used.
+    ///
+    /// Everything except `highest_index` is read from existing bookkeeping,
+    /// `highest_index` scans backwards past trailing free slots.
+    pub fn stats(&self) -> ArenaStats {
+        let highest_index = self
+            .entries
+            .iter()
+            .rposition(|entry| matches!(entry, Entry::Occupied(..)));
+
+        ArenaStats {
+            len: self.length,
+            free: self.free_len(),
+            capacity: self.capacity(),
+            highest_index,
+        }
+    }
+    /// Returns a bitmap of the occupied slots, one bit per slot.
+    ///
+    /// Slot `i` is bit `i % 64` of word `i / 64`, counting from the least
+    /// significant bit, so lower indices are in lower bits. There is one
+    /// word per 64 slots, rounded up, and bits past the last slot are unset.
+    pub fn occupied_mask(&self) -> Vec<u64> {
+        let mut mask = vec![0; self.entries.len().div_ceil(64)];
+        for (index, entry) in self.entries.iter().enumerate() {
+            if let Entry::Occupied(..) = entry {
+                mask[index / 64] |= 1 << (index % 64);
+            }
+        }
+        mask
+    }
+    /// Compares this arena to a `newer` state of it, in index order.
+    ///
+    /// An entry counts as the same in both arenas if it has the same `ID`
+    /// in both. A slot that was reused in between shows up as one removed
+    /// and one added `ID`.
+    pub fn diff<'a, B: Allocator>(&'a self, newer: &'a Arena<T, I, G, B>) -> ArenaDiff<'a, T, I, G>
+    where
+        T: PartialEq,
+    {
+        let mut diff = ArenaDiff {
+            added: Vec::new(),
+            removed: Vec::new(),
+            changed: Vec::new(),
+        };
+        for index in 0..self.entries.len().max(newer.entries.len()) {
+            let old = self.get_by_index(index);
+            let new = newer.get_by_index(index);
+            match (old, new) {
+                (Some((old_id, old)), Some((new_id, new))) if old_id == new_id => {
+                    if old != new {
+                        diff.changed.push((old_id, old, new));
+                    }
+                }
+                _ => {
+                    diff.removed.extend(old.map(|(id, _)| id));
+                    diff.added.extend(new.map(|(id, _)| id));
+                }
+            }
+        }
+        diff
+    }
+    /// Returns a rough estimate of the bytes used by the arena: its own
+    /// size plus the allocated slots, including unused capacity.
+    ///
+    /// Heap memory owned by the values is not counted, see
+    /// [`Arena::memory_usage_with`] for that.
+    pub fn memory_usage(&self) -> usize {
+        size_of::<Self>() + self.entries.capacity() * size_of::<Entry<T, I, G>>()
+    }
+    /// Like [`Arena::memory_usage`], but adds `f` of every value to account
+    /// for the heap memory owned by the values.
+    pub fn memory_usage_with<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(&T) -> usize,
+    {
+        self.memory_usage() + self.values().map(&mut f).sum::<usize>()
+    }
+    /// Checks the internal bookkeeping of the arena against its slots.
+    ///
+    /// This is a debugging aid for tests and fuzzing. It walks every slot,
+    /// so it is not meant for hot paths.
+    pub fn check_invariants(&self) -> Result<(), String> {
+        let occupied = self
+            .entries
+            .iter()
+            .filter(|entry| matches!(entry, Entry::Occupied(..)))
+            .count();
+        if occupied != self.length {
+            return Err(format!("length is {} but {occupied} slots are occupied", self.length));
+        }
+        let retired = self
+            .entries
+            .iter()
+            .filter(|entry| matches!(entry, Entry::Retired))
+            .count();
+        if retired != self.retired {
+            return Err(format!("{} slots should be retired but {retired} are", self.retired));
+        }
+        let reserved = self
+            .entries
+            .iter()
+            .filter(|entry| matches!(entry, Entry::Reserved(..)))
+            .count();
+        if reserved != self.reserved {
+            return Err(format!("{} slots should be reserved but {reserved} are", self.reserved));
+        }
+        if self.entries.len() > I::MAX.to_usize() {
+            return Err(format!("{} slots exceed the index type", self.entries.len()));
+        }
+
+        let head = find_free_list_head(&self.entries).map_err(|err| err.to_string())?;
+        if head != self.free_list_head {
+            return Err(format!(
+                "free list starts at {:?} but should start at {head:?}",
+                self.free_list_head
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn reserve(&mut self, additional: usize) {
+        let additional = self.slots_needed(additional);
+        self.entries.reserve(additional);
+    }
+    pub fn reserve_exact(&mut self, additional: usize) {
+        let additional = self.slots_needed(additional);
+        self.entries.reserve_exact(additional);
+    }
+    fn slots_needed(&self, additional: usize) -> usize {
+        additional.saturating_sub(self.free_len())
+    }
+    /// Reserves room for `additional` entries that are about to be
+    /// inserted, which counts as growing for [`Arena::set_on_grow`].
+    fn reserve_for_insert(&mut self, additional: usize) {
+        let capacity = self.entries.capacity();
+        self.reserve(additional);
+        self.notify_grow(capacity);
+    }
+    fn notify_grow(&self, old_capacity: usize) {
+        if let Some(on_grow) = self.on_grow {
+            if self.entries.capacity() != old_capacity {
+                on_grow(old_capacity, self.entries.capacity());
+            }
+        }
+    }
+    /// Shrinks the backing storage as much as possible.
+    ///
+    /// Free slots at the end of the arena are dropped first. Slots behind
+    /// the last occupied one are never needed to keep existing IDs valid,
+    /// and slots created later continue counting from the highest
+    /// generation among the dropped ones, so stale IDs stay stale.
+    pub fn shrink_to_fit(&mut self) {
+        self.trim_free_tail();
+        self.entries.shrink_to_fit();
+    }
+    /// Like [`Arena::shrink_to_fit`], but keeps a capacity of at least
+    /// `min_capacity` slots.
+    ///
+    /// The capacity never drops below the slots still in use, up to the
+    /// last slot that is not free.
+    pub fn shrink_to(&mut self, min_capacity: usize) {
+        self.trim_free_tail();
+        self.entries.shrink_to(min_capacity);
+    }
+    fn trim_free_tail(&mut self) {
+        let len = self
+            .entries
+            .iter()
+            .rposition(|entry| !matches!(entry, Entry::Free { .. }))
+            .map_or(0, |index| index + 1);
+        if len == self.entries.len() {
+            return;
+        }
 
-    pub fn insert(&mut self, t: T) -> ID {
+        let mut current = self.free_list_head.take();
+        let mut last_kept = None;
+        while let Some(index) = current.map(I::to_usize) {
+            let Entry::Free { next_free, .. } = self.entries[index] else { unreachable!() };
+            current = next_free;
+            if index >= len {
+                continue;
+            }
+
+            match last_kept {
+                Some(last) => self.set_next_free(last, Some(index)),
+                None => self.free_list_head = Some(I::from_usize(index)),
+            }
+            last_kept = Some(index);
+        }
+        if let Some(last) = last_kept {
+            self.set_next_free(last, None);
+        }
+
+        for entry in self.entries.drain(len..) {
+            let Entry::Free { next_generation, .. } = entry else { unreachable!() };
+            self.fresh_generation = self.fresh_generation.max(next_generation);
+        }
+    }
+    /// Moves entries from the back of the arena into free slots at the front
+    /// until there are no more holes, then trims the now unused tail.
+    ///
+    /// Returns a map from the old `ID` of every entry to its new `ID`.
+    /// Entries that did not have to move keep their `ID`. A moved entry
+    /// takes on the next generation of the slot it moves into, so stale
+    /// IDs of that slot stay stale.
+    ///
+    /// Reserved and retired slots are neither moved nor filled, so they
+    /// stay behind as holes, and so do free slots in front of them once
+    /// no occupied slot is left behind those free slots.
+    #[cfg(feature = "std")]
+    pub fn compact(&mut self) -> IdRemap<T, I, G> {
+        let mut remap: HashMap<_, _> = self.indices().map(|id| (id, id)).collect();
+        self.compact_with(|old, new| {
+            remap.insert(old, new);
+        });
+        remap
+    }
+    /// Compacts the arena like [`Arena::compact`], but calls `f` with the
+    /// old and new `ID` of every entry that moves instead of building a map.
+    pub fn compact_with<F>(&mut self, mut f: F)
+    where
+        F: FnMut(ID<T, I, G>, ID<T, I, G>),
+    {
+        let mut front = 0;
+        let mut back = self.entries.len();
+        loop {
+            while front < back && !matches!(self.entries[front], Entry::Free { .. }) {
+                front += 1;
+            }
+            while back > front && !matches!(self.entries[back - 1], Entry::Occupied(..)) {
+                back -= 1;
+            }
+            if front >= back {
+                break;
+            }
+
+            let from = back - 1;
+            let Entry::Free { next_generation, .. } = self.entries[front] else { unreachable!() };
+            let vacated = self.vacated_entry(from);
+            let entry = core::mem::replace(&mut self.entries[from], vacated);
+            let Entry::Occupied(gen, t) = entry else { unreachable!() };
+            self.entries[front] = Entry::Occupied(next_generation, t);
+
+            f(ID::new(from, gen), ID::new(front, next_generation));
+        }
+
+        self.rebuild_free_list();
+        self.trim_free_tail();
+    }
+    fn vacated_entry(&mut self, index: usize) -> Entry<T, I, G> {
+        let Entry::Occupied(gen, _) = self.entries[index] else { unreachable!() };
+        match G::next(gen) {
+            Some(next_generation) => Entry::Free {
+                next_generation,
+                next_free: None,
+            },
+            None => {
+                self.retired += 1;
+                Entry::Retired
+            }
+        }
+    }
+    /// Takes the value out of the occupied slot at `index` and frees the
+    /// slot like [`Arena::remove`] would, except for updating `length`.
+    fn vacate(&mut self, index: usize) -> (G::Raw, T) {
+        let vacated = self.vacated_entry(index);
+        let entry = core::mem::replace(&mut self.entries[index], vacated);
+        if let Entry::Free { next_free, .. } = &mut self.entries[index] {
+            *next_free = self.free_list_head.replace(I::from_usize(index));
+        }
+        let Entry::Occupied(gen, t) = entry else { unreachable!() };
+        (gen, t)
+    }
+    fn rebuild_free_list(&mut self) {
+        let mut next = None;
+        for (index, entry) in self.entries.iter_mut().enumerate().rev() {
+            if let Entry::Free { next_free, .. } = entry {
+                *next_free = next;
+                next = Some(I::from_usize(index));
+            }
+        }
+        self.free_list_head = next;
+    }
+    fn set_next_free(&mut self, index: usize, next: Option<usize>) {
+        let Entry::Free { next_free, .. } = &mut self.entries[index] else { unreachable!() };
+        *next_free = next.map(I::from_usize);
+    }
+
+    pub fn insert(&mut self, t: T) -> ID<T, I, G> {
         self.insert_with_id(|_| t)
     }
-    pub fn insert_with_id<F>(&mut self, f: F) -> ID
+    /// Inserts every item and returns their `ID`s in the same order.
+    ///
+    /// Free slots are reused first, and only the slots still missing
+    /// after that are allocated, all at once.
+    pub fn insert_batch(&mut self, items: Vec<T>) -> Vec<ID<T, I, G>> {
+        self.reserve_for_insert(items.len());
+        items.into_iter().map(|t| self.insert(t)).collect()
+    }
+    /// Inserts `t` unless the arena has reached its maximum length,
+    /// in which case `t` is handed back.
+    ///
+    /// Arenas without a maximum length never return `Err`.
+    pub fn try_insert(&mut self, t: T) -> Result<ID<T, I, G>, T> {
+        if self.is_full() {
+            return Err(t);
+        }
+        Ok(self.insert(t))
+    }
+    /// Moves every entry of `other` into this arena.
+    ///
+    /// Returns a map from the `ID` of every entry in `other` to its new
+    /// `ID` in this arena, for fixing up references between the moved values.
+    #[cfg(feature = "std")]
+    pub fn append<B: Allocator>(&mut self, other: Arena<T, I, G, B>) -> IdRemap<T, I, G> {
+        self.reserve_for_insert(other.len());
+        let mut remap = HashMap::with_capacity(other.len());
+        for (index, entry) in other.entries.into_iter().enumerate() {
+            if let Entry::Occupied(gen, t) = entry {
+                remap.insert(ID::new(index, gen), self.insert(t));
+            }
+        }
+        remap
+    }
+    pub fn insert_with_id<F>(&mut self, f: F) -> ID<T, I, G>
     where
-        F: FnOnce(ID) -> T,
+        F: FnOnce(ID<T, I, G>) -> T,
     {
-        let id = if let Some(free) = self.free_list_head.take() {
+        // Only allocate the slot once `f` has returned, so that a panic
+        // in `f` leaves the arena untouched.
+        assert!(!self.is_full(), "arena is full");
+        let id = self.peek_next_id();
+        let item = f(id);
+
+        let allocated = self.allocate();
+        debug_assert_eq!(id, allocated);
+        self.entries[id.index()] = Entry::Occupied(id.generation, item);
+        self.length += 1;
+
+        id
+    }
+    /// Allocates a slot and returns its `ID` without giving it a value.
+    ///
+    /// The slot does not count as occupied: `get` returns `None` and
+    /// iterators skip it until it is given a value with [`Arena::fill`].
+    /// Until then the returned `ID` is effectively dangling, and a slot
+    /// that is never filled stays in use until the arena is cleared.
+    ///
+    /// Panics if the arena is full.
+    pub fn reserve_id(&mut self) -> ID<T, I, G> {
+        let id = self.allocate();
+        self.entries[id.index()] = Entry::Reserved(id.generation);
+        self.reserved += 1;
+        id
+    }
+    /// Reserves `n` slots like [`Arena::reserve_id`] and returns their
+    /// `ID`s, e.g. so that values can refer to each other before any of
+    /// them exists.
+    ///
+    /// Panics if fewer than `n` slots are left, without reserving any.
+    pub fn reserve_ids(&mut self, n: usize) -> Vec<ID<T, I, G>> {
+        if let Some(max_len) = self.max_len {
+            assert!(self.length + self.reserved + n <= max_len, "arena is full");
+        }
+        self.reserve_for_insert(n);
+        (0..n).map(|_| self.reserve_id()).collect()
+    }
+    /// Gives the slot reserved by [`Arena::reserve_id`] its value.
+    ///
+    /// Panics if `id` is not currently reserved.
+    pub fn fill(&mut self, id: ID<T, I, G>, t: T) {
+        let entry = self.entries.get_mut(id.index());
+        assert!(
+            matches!(entry, Some(Entry::Reserved(gen)) if *gen == id.generation),
+            "ID {id} is not reserved"
+        );
+
+        self.entries[id.index()] = Entry::Occupied(id.generation, t);
+        self.reserved -= 1;
+        self.length += 1;
+    }
+    fn allocate(&mut self) -> ID<T, I, G> {
+        assert!(!self.is_full(), "arena is full");
+
+        if let Some(free) = self.free_list_head.take() {
+            let free = free.to_usize();
             let &Entry::Free { next_generation, next_free } = &self.entries[free] else { unreachable!() };
             self.free_list_head = next_free;
 
             ID::new(free, next_generation)
         } else {
             let index = self.entries.len();
+            assert!(index < I::MAX.to_usize(), "arena index {index} is reserved for ID::NULL");
+
+            let id = ID::new(index, self.fresh_generation);
+            let capacity = self.entries.capacity();
             self.entries.push(Entry::Free {
-                next_generation: 0,
+                next_generation: self.fresh_generation,
                 next_free: None,
             });
-            ID::new(index, 0)
+            self.notify_grow(capacity);
+            id
+        }
+    }
+    /// Returns the value behind `id`, inserting the result of `f` if
+    /// `id` can be brought back to life.
+    ///
+    /// That is the case if the slot of `id` is free and its next
+    /// generation is the generation of `id`, i.e. if the next insert
+    /// into that slot would hand out exactly `id`.
+    ///
+    /// Panics if `id` is neither valid nor can be brought back to life.
+    pub fn get_or_insert_with<F>(&mut self, id: ID<T, I, G>, f: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        if !self.contains(id) {
+            assert!(self.can_resurrect(id), "ID {id} is neither valid nor can be resurrected");
+            self.occupy_free(id, f());
+        }
+        self.get_mut(id).unwrap()
+    }
+    /// Makes a removed `id` valid again, with the value `t`.
+    ///
+    /// This only succeeds if the slot of `id` is still free and has not
+    /// been reused since `id` was removed. Otherwise, or if the arena is
+    /// full, `t` is handed back.
+    ///
+    /// Removing `id` already moved its slot on to the next generation,
+    /// so this checks that the slot's next generation directly follows
+    /// the generation of `id` rather than being equal to it.
+    pub fn resurrect(&mut self, id: ID<T, I, G>, t: T) -> Result<(), T> {
+        let unused = match self.entries.get(id.index()) {
+            Some(Entry::Free { next_generation, .. }) => G::next(id.generation) == Some(*next_generation),
+            _ => false,
         };
+        if self.is_full() || !unused {
+            return Err(t);
+        }
+
+        self.occupy_free(id, t);
+        Ok(())
+    }
+    /// Places `t` at exactly `id`, growing the arena if needed.
+    ///
+    /// This is meant for restoring entries at the IDs they had before,
+    /// so it does not check `id` against the generation the slot would
+    /// hand out next.
+    pub fn insert_at(&mut self, id: ID<T, I, G>, t: T) -> Result<(), InsertAtError> {
+        if id.is_null() {
+            return Err(InsertAtError::Null);
+        }
+
+        let index = id.index();
+        match self.entries.get(index) {
+            Some(Entry::Occupied(..)) => return Err(InsertAtError::Occupied),
+            Some(Entry::Retired) => return Err(InsertAtError::Retired),
+            Some(Entry::Reserved(..)) => return Err(InsertAtError::Reserved),
+            _ if self.is_full() => return Err(InsertAtError::Full),
+            Some(Entry::Free { .. }) => {
+                self.unlink_free(index);
+                self.entries[index] = Entry::Occupied(id.generation, t);
+            }
+            None => {
+                let capacity = self.entries.capacity();
+                let additional = index + 1 - self.entries.len();
+                self.entries.reserve(additional);
+                while self.entries.len() < index {
+                    let next_free = self.free_list_head.replace(I::from_usize(self.entries.len()));
+                    self.entries.push(Entry::Free {
+                        next_generation: self.fresh_generation,
+                        next_free,
+                    });
+                }
+                self.entries.push(Entry::Occupied(id.generation, t));
+                self.notify_grow(capacity);
+            }
+        }
+
         self.length += 1;
+        Ok(())
+    }
+    fn can_resurrect(&self, id: ID<T, I, G>) -> bool {
+        matches!(
+            self.entries.get(id.index()),
+            Some(Entry::Free { next_generation, .. }) if *next_generation == id.generation
+        )
+    }
+    fn occupy_free(&mut self, id: ID<T, I, G>, t: T) {
+        assert!(!self.is_full(), "arena is full");
 
-        let item = f(id);
-        self.entries[id.index] = Entry::Occupied(id.generation, item);
+        self.unlink_free(id.index());
+        self.entries[id.index()] = Entry::Occupied(id.generation, t);
+        self.length += 1;
+    }
+    fn unlink_free(&mut self, index: usize) {
+        let Entry::Free { next_free: after, .. } = self.entries[index] else { unreachable!() };
 
-        id
+        let mut previous = None;
+        let mut current = self.free_list_head.map(I::to_usize);
+        while let Some(free) = current {
+            if free == index {
+                break;
+            }
+            let Entry::Free { next_free, .. } = self.entries[free] else { unreachable!() };
+            previous = Some(free);
+            current = next_free.map(I::to_usize);
+        }
+
+        match previous {
+            Some(previous) => self.set_next_free(previous, after.map(I::to_usize)),
+            None => self.free_list_head = after,
+        }
     }
-    pub fn remove(&mut self, id: ID) -> Option<T> {
+
+    /// Moves the entry with `id` into the free slot at `target_index`
+    /// and returns its new `ID`.
+    ///
+    /// The entry takes on the next generation of the target slot, and its
+    /// old slot is freed, so `id` becomes invalid. Fails if `id` is not
+    /// valid or the target slot is not free or does not exist yet.
+    pub fn move_to(&mut self, id: ID<T, I, G>, target_index: usize) -> Result<ID<T, I, G>, MoveError> {
+        if !self.contains(id) {
+            return Err(MoveError::InvalidId);
+        }
+        let next_generation = match self.entries.get(target_index) {
+            Some(&Entry::Free { next_generation, .. }) => next_generation,
+            Some(_) => return Err(MoveError::NotFree),
+            None => return Err(MoveError::OutOfBounds),
+        };
+
+        self.unlink_free(target_index);
+        let (_, t) = self.vacate(id.index());
+        self.entries[target_index] = Entry::Occupied(next_generation, t);
+        Ok(ID::new(target_index, next_generation))
+    }
+    /// Saves the current state of the arena, to be restored with
+    /// [`Arena::rollback`].
+    ///
+    /// The checkpoint is a full copy of the arena, so every operation is
+    /// reversible, but taking it costs as much as cloning the arena.
+    pub fn checkpoint(&self) -> Checkpoint<T, I, G, A>
+    where
+        T: Clone,
+        I: Clone,
+        A: Clone,
+    {
+        Checkpoint { arena: self.clone() }
+    }
+    /// Restores the state saved in `checkpoint`.
+    ///
+    /// Everything done since the checkpoint is undone: entries inserted
+    /// since then are dropped, and every `ID` that was valid at the time of
+    /// the checkpoint is valid again. `checkpoint` should have been taken
+    /// from this arena, otherwise this simply replaces it with the other one.
+    pub fn rollback(&mut self, checkpoint: Checkpoint<T, I, G, A>) {
+        *self = checkpoint.arena;
+    }
+
+    /// Returns the `ID` the next call to [`Arena::insert`] will return.
+    pub fn peek_next_id(&self) -> ID<T, I, G> {
+        match self.free_list_head.map(I::to_usize) {
+            Some(free) => {
+                let Entry::Free { next_generation, .. } = self.entries[free] else { unreachable!() };
+                ID::new(free, next_generation)
+            }
+            None => ID::new(self.entries.len(), self.fresh_generation),
+        }
+    }
+    /// Removes the value behind `id`, invalidating `id`.
+    ///
+    /// If the slot's generation cannot be incremented any further,
+    /// the slot is retired instead of being put back on the free list,
+    /// so that no later `ID` can ever compare equal to `id`.
+    pub fn remove(&mut self, id: ID<T, I, G>) -> Option<T> {
         if !self.contains(id) {
             return None;
         }
+        // `contains` only accepts `id` if its slot is occupied with its exact
+        // generation, which is what keeps `length` in sync with the slots.
+        debug_assert!(
+            matches!(self.entries[id.index()], Entry::Occupied(gen, _) if gen == id.generation),
+            "arena slot {} is not occupied by {id}",
+            id.index()
+        );
 
-        let new_entry = Entry::Free {
-            next_free: self.free_list_head,
-            next_generation: id.generation + 1,
+        let new_entry = if let Some(next_generation) = G::next(id.generation) {
+            let next_free = self.free_list_head.replace(id.index);
+            Entry::Free {
+                next_free,
+                next_generation,
+            }
+        } else {
+            self.retired += 1;
+            Entry::Retired
         };
-        let old_entry = std::mem::replace(&mut self.entries[id.index], new_entry);
+        let old_entry = core::mem::replace(&mut self.entries[id.index()], new_entry);
 
-        self.free_list_head = Some(id.index);
+        let Entry::Occupied(_, item) = old_entry else { panic!("removed arena slot {} was not occupied", id.index()) };
+        self.length -= 1;
+        Some(item)
+    }
+    /// Removes the entry with `id` and fills its slot with the occupied
+    /// entry with the highest index, keeping the occupied entries dense.
+    ///
+    /// Unlike [`Arena::remove`], this does not keep the `ID`s of other
+    /// entries stable: if an entry was moved, its old and new `ID` are
+    /// returned alongside the value. The moved entry takes on the next
+    /// generation of the slot it moves into, so `id` stays stale. Nothing
+    /// is moved if `id` was the last occupied entry or its slot retires.
+    pub fn remove_compacting(&mut self, id: ID<T, I, G>) -> Option<CompactingRemoval<T, I, G>> {
+        if !self.contains(id) {
+            return None;
+        }
+        let last = self.iter().next_back().map(|(last, _)| last.index());
+        let hole = id.index();
+        let next_generation = G::next(id.generation);
+        let (Some(last), Some(next_generation)) = (last.filter(|&last| last != hole), next_generation) else {
+            return self.remove(id).map(|t| (t, None));
+        };
 
+        let (gen, moved) = self.vacate(last);
+        let old_entry = core::mem::replace(&mut self.entries[hole], Entry::Occupied(next_generation, moved));
         let Entry::Occupied(_, item) = old_entry else { unreachable!() };
         self.length -= 1;
-        Some(item)
+
+        Some((item, Some((ID::new(last, gen), ID::new(hole, next_generation)))))
     }
+    /// Removes every entry whose slot index lies in `range` and returns
+    /// them in index order.
+    ///
+    /// Like [`Arena::iter_range`], the range is over slot indices and is
+    /// clamped to the slots of the arena.
+    pub fn remove_index_range(&mut self, range: impl RangeBounds<usize>) -> Vec<(ID<T, I, G>, T)> {
+        // The freed slots are chained in ascending order and then spliced
+        // onto the front of the free list at once.
+        let mut removed = Vec::new();
+        let mut chain: Option<(usize, usize)> = None;
+        for index in self.slot_range(range) {
+            let Entry::Occupied(..) = self.entries[index] else { continue };
+            let vacated = self.vacated_entry(index);
+            let is_free = matches!(vacated, Entry::Free { .. });
+            let Entry::Occupied(gen, t) = core::mem::replace(&mut self.entries[index], vacated) else { unreachable!() };
+            removed.push((ID::new(index, gen), t));
 
-    pub fn get(&self, id: ID) -> Option<&T> {
-        let Some(entry) = self.entries.get(id.index) else { return None };
+            if is_free {
+                chain = match chain {
+                    Some((first, last)) => {
+                        self.set_next_free(last, Some(index));
+                        Some((first, index))
+                    }
+                    None => Some((index, index)),
+                };
+            }
+        }
+        if let Some((first, last)) = chain {
+            let head = self.free_list_head.replace(I::from_usize(first));
+            self.set_next_free(last, head.map(I::to_usize));
+        }
+        self.length -= removed.len();
+        removed
+    }
+    /// Removes every valid `ID` in `ids`, in order, and returns how many
+    /// entries were actually removed.
+    pub fn remove_many<It>(&mut self, ids: It) -> usize
+    where
+        It: IntoIterator<Item = ID<T, I, G>>,
+    {
+        ids.into_iter().filter(|&id| self.remove(id).is_some()).count()
+    }
+    pub fn clear(&mut self) {
+        let mut next_free = None;
+        for (index, entry) in self.entries.iter_mut().enumerate().rev() {
+            let next_generation = match entry {
+                Entry::Free {
+                    next_generation, ..
+                } => *next_generation,
+                Entry::Occupied(gen, _) | Entry::Reserved(gen) => match G::next(*gen) {
+                    Some(next_generation) => next_generation,
+                    None => {
+                        *entry = Entry::Retired;
+                        self.retired += 1;
+                        continue;
+                    }
+                },
+                Entry::Retired => continue,
+            };
+            *entry = Entry::Free {
+                next_generation,
+                next_free,
+            };
+            next_free = Some(I::from_usize(index));
+        }
+
+        self.free_list_head = next_free;
+        self.length = 0;
+        self.reserved = 0;
+    }
+    /// Clears the arena like [`Arena::clear`] and releases as much memory
+    /// as possible like [`Arena::shrink_to_fit`].
+    ///
+    /// Slots up to the last retired one are kept, free slots before it
+    /// included, so the arena only ends up without any allocation if none
+    /// of its slots have retired.
+    pub fn clear_and_shrink(&mut self) {
+        self.clear();
+        self.shrink_to_fit();
+    }
+    /// Resets the generation of every slot, including retired ones, to the
+    /// first generation, so the arena hands out the same `ID`s as a new one.
+    ///
+    /// This breaks the guarantee that a stale `ID` never resolves again:
+    /// every `ID` issued before must be considered invalid. To make that
+    /// explicit, this fails unless the arena has no live or reserved entries.
+    pub fn reset_generations(&mut self) -> Result<(), NotEmptyError> {
+        if self.length != 0 || self.reserved != 0 {
+            return Err(NotEmptyError);
+        }
+
+        for entry in self.entries.iter_mut() {
+            *entry = Entry::Free {
+                next_generation: G::FIRST,
+                next_free: None,
+            };
+        }
+        self.retired = 0;
+        self.fresh_generation = G::FIRST;
+        self.rebuild_free_list();
+        Ok(())
+    }
+    /// Counts the entries for which `f` returns true, e.g. to check
+    /// whether a [`Arena::retain`] is worth it.
+    ///
+    /// Stops scanning after the last occupied slot.
+    pub fn count_matching<F>(&self, mut f: F) -> usize
+    where
+        F: FnMut(ID<T, I, G>, &T) -> bool,
+    {
+        self.iter().filter(|&(id, t)| f(id, t)).count()
+    }
+    /// Removes every entry for which `f` returns false.
+    ///
+    /// If anything was removed, the free list is rebuilt in ascending index
+    /// order afterwards, so later inserts fill the lowest holes first
+    /// instead of going wherever the last removal happened to be.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(ID<T, I, G>, &mut T) -> bool,
+    {
+        let mut removed = false;
+        for index in 0..self.entries.len() {
+            let Entry::Occupied(gen, item) = &mut self.entries[index] else { continue };
+            let id = ID::new(index, *gen);
+
+            if !f(id, item) {
+                self.remove(id);
+                removed = true;
+            }
+        }
+        if removed {
+            self.rebuild_free_list();
+        }
+    }
+    /// Like [`Arena::retain`], but returns the now invalid `ID`s of the
+    /// removed entries, in removal order.
+    pub fn retain_collect<F>(&mut self, mut f: F) -> Vec<ID<T, I, G>>
+    where
+        F: FnMut(ID<T, I, G>, &mut T) -> bool,
+    {
+        let mut removed = Vec::new();
+        self.retain(|id, t| {
+            let keep = f(id, t);
+            if !keep {
+                removed.push(id);
+            }
+            keep
+        });
+        removed
+    }
+    /// Removes every entry whose key, as given by `key_of`, is not in `keep`,
+    /// and returns the `ID`s of the removed entries.
+    ///
+    /// Entries sharing a key are all kept if the key is in `keep`.
+    #[cfg(feature = "std")]
+    pub fn reconcile<K, F>(&mut self, keep: &HashSet<K>, key_of: F) -> Vec<ID<T, I, G>>
+    where
+        K: Eq + Hash,
+        F: Fn(&T) -> K,
+    {
+        self.retain_collect(|_, t| keep.contains(&key_of(t)))
+    }
+    /// Like [`Arena::retain`], but `f` can also read the whole arena.
+    ///
+    /// This runs in two passes: `f` is first called for every entry with
+    /// the arena as it was before the call, and only then are the entries
+    /// it rejected removed. So `f` sees every entry, including ones that
+    /// it already decided to remove.
+    pub fn retain_cross<F>(&mut self, mut f: F)
+    where
+        F: FnMut(ID<T, I, G>, &T, &Self) -> bool,
+    {
+        let doomed: Vec<_> = self
+            .iter()
+            .filter(|&(id, t)| !f(id, t, self))
+            .map(|(id, _)| id)
+            .collect();
+        if doomed.is_empty() {
+            return;
+        }
+        for id in doomed {
+            self.remove(id);
+        }
+        self.rebuild_free_list();
+    }
+    /// Moves every entry for which `pred` returns true into a new arena.
+    ///
+    /// Returns the new arena along with a map from the old `ID` of every
+    /// moved entry to its `ID` in the new arena. The remaining entries
+    /// keep their `ID`s.
+    #[cfg(feature = "std")]
+    pub fn split_off<F>(&mut self, mut pred: F) -> (Arena<T, I, G>, IdRemap<T, I, G>)
+    where
+        F: FnMut(ID<T, I, G>, &T) -> bool,
+    {
+        let mut other = Arena::default();
+        let remap = self
+            .extract_if(|id, t| pred(id, t))
+            .map(|(id, t)| (id, other.insert(t)))
+            .collect();
+        (other, remap)
+    }
+    /// Clones the entries of the valid `ID`s in `ids` into a new arena,
+    /// skipping invalid and repeated ones.
+    ///
+    /// Returns the new arena along with a map from the old `ID` of every
+    /// cloned entry to its `ID` in the new arena.
+    #[cfg(feature = "std")]
+    pub fn clone_subset<It>(&self, ids: It) -> (Arena<T, I, G>, IdRemap<T, I, G>)
+    where
+        T: Clone,
+        It: IntoIterator<Item = ID<T, I, G>>,
+    {
+        let mut subset = Arena::default();
+        let mut remap = HashMap::new();
+        for id in ids {
+            let Some(t) = self.get(id) else { continue };
+            remap.entry(id).or_insert_with(|| subset.insert(t.clone()));
+        }
+        (subset, remap)
+    }
+    /// Moves every entry into a `HashMap` keyed by its `ID`.
+    #[cfg(feature = "std")]
+    pub fn into_id_map(self) -> HashMap<ID<T, I, G>, T> {
+        self.into_ids_values().collect()
+    }
+    /// Collects a reference to every entry into a `HashMap` keyed by its `ID`.
+    #[cfg(feature = "std")]
+    pub fn id_map(&self) -> HashMap<ID<T, I, G>, &T> {
+        self.iter().collect()
+    }
+
+    /// Splits the arena into the entries for which `f` returns true and
+    /// those for which it returns false.
+    ///
+    /// Returns both arenas along with a map from the old `ID` of every
+    /// entry to its `ID` in whichever arena it landed in.
+    #[cfg(feature = "std")]
+    pub fn partition<F>(self, mut f: F) -> Partition<T, I, G>
+    where
+        F: FnMut(ID<T, I, G>, &T) -> bool,
+    {
+        let mut matching = Arena::default();
+        let mut rest = Arena::default();
+        let mut remap = HashMap::with_capacity(self.len());
+        for (id, t) in self.into_ids_values() {
+            let new_id = if f(id, &t) { matching.insert(t) } else { rest.insert(t) };
+            remap.insert(id, new_id);
+        }
+        (matching, rest, remap)
+    }
+    /// Returns an iterator that removes and yields every entry
+    /// for which `f` returns true.
+    ///
+    /// If the iterator is dropped early, the remaining matching entries
+    /// are still removed. Once it is dropped, the free list is rebuilt in
+    /// ascending index order like after [`Arena::retain`].
+    pub fn extract_if<F>(&mut self, f: F) -> ExtractIf<'_, T, I, G, F, A>
+    where
+        F: FnMut(ID<T, I, G>, &mut T) -> bool,
+    {
+        ExtractIf {
+            arena: self,
+            index: 0,
+            removed: false,
+            in_predicate: false,
+            f,
+        }
+    }
+
+    /// Transforms every value, keeping the exact slot layout.
+    ///
+    /// Every `ID` of this arena, once rebranded with [`ID::rebrand`],
+    /// refers to the transformed value in the result.
+    pub fn map<U, F>(self, mut f: F) -> Arena<U, I, G>
+    where
+        F: FnMut(ID<T, I, G>, T) -> U,
+    {
+        let entries: Vec<_> = self
+            .entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| entry.map(|gen, t| f(ID::new(index, gen), t)))
+            .collect();
+
+        Arena {
+            entries: storage(entries),
+            free_list_head: self.free_list_head,
+            length: self.length,
+            retired: self.retired,
+            reserved: self.reserved,
+            max_len: self.max_len,
+            fresh_generation: self.fresh_generation,
+            on_grow: self.on_grow,
+        }
+    }
+    /// Like [`Arena::map`], but borrows the values instead of consuming them.
+    pub fn map_ref<U, F>(&self, mut f: F) -> Arena<U, I, G>
+    where
+        F: FnMut(ID<T, I, G>, &T) -> U,
+    {
+        let entries: Vec<_> = self
+            .entries
+            .iter()
+            .enumerate()
+            .map(|(index, entry)| entry.as_ref().map(|gen, t| f(ID::new(index, gen), t)))
+            .collect();
+
+        Arena {
+            entries: storage(entries),
+            free_list_head: self.free_list_head,
+            length: self.length,
+            retired: self.retired,
+            reserved: self.reserved,
+            max_len: self.max_len,
+            fresh_generation: self.fresh_generation,
+            on_grow: self.on_grow,
+        }
+    }
+
+    /// Transforms every value and drops those for which `f` returns `None`.
+    ///
+    /// Unlike a compacting transform, this preserves `ID`s: surviving
+    /// entries keep their slot and generation, so every surviving `ID`,
+    /// once rebranded, still resolves in the result.
+    /// Dropped entries become free slots with a bumped generation.
+    pub fn filter_map<U, F>(self, mut f: F) -> Arena<U, I, G>
+    where
+        F: FnMut(ID<T, I, G>, T) -> Option<U>,
+    {
+        let mut length = 0;
+        let mut retired = self.retired;
+        let entries: Vec<_> = self
+            .entries
+            .into_iter()
+            .enumerate()
+            .map(|(index, entry)| match entry {
+                Entry::Free {
+                    next_generation,
+                    next_free,
+                } => Entry::Free {
+                    next_generation,
+                    next_free,
+                },
+                Entry::Occupied(gen, t) => match f(ID::new(index, gen), t) {
+                    Some(u) => {
+                        length += 1;
+                        Entry::Occupied(gen, u)
+                    }
+                    None => match G::next(gen) {
+                        Some(next_generation) => Entry::Free {
+                            next_generation,
+                            next_free: None,
+                        },
+                        None => {
+                            retired += 1;
+                            Entry::Retired
+                        }
+                    },
+                },
+                Entry::Reserved(gen) => Entry::Reserved(gen),
+                Entry::Retired => Entry::Retired,
+            })
+            .collect();
+
+        let mut arena = Arena {
+            entries: storage(entries),
+            free_list_head: None,
+            length,
+            retired,
+            reserved: self.reserved,
+            max_len: self.max_len,
+            fresh_generation: self.fresh_generation,
+            on_grow: self.on_grow,
+        };
+        arena.rebuild_free_list();
+        arena
+    }
+
+    pub fn get(&self, id: ID<T, I, G>) -> Option<&T> {
+        let entry = self.entries.get(id.index())?;
         let Entry::Occupied(gen, item) = entry else { return None };
 
         if id.generation != *gen {
@@ -84,8 +1322,8 @@ impl<T> Arena<T> {
             Some(item)
         }
     }
-    pub fn get_mut(&mut self, id: ID) -> Option<&mut T> {
-        let Some(entry) = self.entries.get_mut(id.index) else { return None };
+    pub fn get_mut(&mut self, id: ID<T, I, G>) -> Option<&mut T> {
+        let entry = self.entries.get_mut(id.index())?;
         let Entry::Occupied(gen, item) = entry else { return None };
 
         if id.generation != *gen {
@@ -94,79 +1332,572 @@ impl<T> Arena<T> {
             Some(item)
         }
     }
-    pub fn contains(&self, id: ID) -> bool {
-        self.get(id).is_some()
+    /// Applies `f` to the value behind `id`, returning whether `id` was valid.
+    pub fn modify<F>(&mut self, id: ID<T, I, G>, f: F) -> bool
+    where
+        F: FnOnce(&mut T),
+    {
+        self.get_mut(id).map(f).is_some()
+    }
+    /// Like [`Arena::get`], but reports why `id` could not be resolved.
+    pub fn try_get(&self, id: ID<T, I, G>) -> Result<&T, AccessError<G>> {
+        let entry = self.entries.get(id.index()).ok_or(AccessError::OutOfBounds)?;
+        let Entry::Occupied(gen, item) = entry else { return Err(AccessError::Vacant) };
+
+        if id.generation != *gen {
+            Err(AccessError::StaleGeneration {
+                expected: id.generation(),
+                found: G::from_raw(*gen),
+            })
+        } else {
+            Ok(item)
+        }
+    }
+    /// Mutable version of [`Arena::try_get`].
+    pub fn try_get_mut(&mut self, id: ID<T, I, G>) -> Result<&mut T, AccessError<G>> {
+        let entry = self.entries.get_mut(id.index()).ok_or(AccessError::OutOfBounds)?;
+        let Entry::Occupied(gen, item) = entry else { return Err(AccessError::Vacant) };
+
+        if id.generation != *gen {
+            Err(AccessError::StaleGeneration {
+                expected: id.generation(),
+                found: G::from_raw(*gen),
+            })
+        } else {
+            Ok(item)
+        }
+    }
+    /// Returns the value of `id` without checking bounds or generation.
+    ///
+    /// # Safety
+    ///
+    /// `id` must currently be valid in this arena, as reported by
+    /// [`Arena::contains`]. Calling this with an out of bounds, vacant or
+    /// stale `ID` is undefined behavior, even if the result is unused.
+    pub unsafe fn get_unchecked(&self, id: ID<T, I, G>) -> &T {
+        // SAFETY: the caller guarantees that `id` is in bounds and occupied.
+        match unsafe { self.entries.get_unchecked(id.index()) } {
+            Entry::Occupied(_, item) => item,
+            _ => unsafe { core::hint::unreachable_unchecked() },
+        }
+    }
+    /// Mutable version of [`Arena::get_unchecked`].
+    ///
+    /// # Safety
+    ///
+    /// `id` must currently be valid in this arena, as reported by
+    /// [`Arena::contains`]. Calling this with an out of bounds, vacant or
+    /// stale `ID` is undefined behavior, even if the result is unused.
+    pub unsafe fn get_unchecked_mut(&mut self, id: ID<T, I, G>) -> &mut T {
+        // SAFETY: the caller guarantees that `id` is in bounds and occupied.
+        match unsafe { self.entries.get_unchecked_mut(id.index()) } {
+            Entry::Occupied(_, item) => item,
+            _ => unsafe { core::hint::unreachable_unchecked() },
+        }
+    }
+    /// Looks up every `ID` in `ids` on its own, so invalid `ID`s only
+    /// give `None` in their place and duplicates are allowed.
+    pub fn get_many<const N: usize>(&self, ids: [ID<T, I, G>; N]) -> [Option<&T>; N] {
+        ids.map(|id| self.get(id))
+    }
+    pub fn get_disjoint_mut<const N: usize>(&mut self, ids: [ID<T, I, G>; N]) -> Option<[&mut T; N]> {
+        if !ids.iter().all(|&id| self.contains(id)) {
+            return None;
+        }
+
+        self.get_disjoint_index_mut(ids.map(|id| id.index()))
+    }
+    /// Like [`Arena::get_disjoint_mut`], but takes slot indices instead of
+    /// `ID`s and so does not check generations.
+    ///
+    /// Returns `None` if any index is duplicated, out of bounds or not occupied.
+    pub fn get_disjoint_index_mut<const N: usize>(&mut self, indices: [usize; N]) -> Option<[&mut T; N]> {
+        let entries = self.entries.get_disjoint_mut(indices).ok()?;
+        if !entries.iter().all(|entry| matches!(entry, Entry::Occupied(..))) {
+            return None;
+        }
+        Some(entries.map(|entry| {
+            let Entry::Occupied(_, item) = entry else { unreachable!() };
+            item
+        }))
+    }
+    pub fn get2_mut(&mut self, a: ID<T, I, G>, b: ID<T, I, G>) -> Option<(&mut T, &mut T)> {
+        let [a, b] = self.get_disjoint_mut([a, b])?;
+        Some((a, b))
+    }
+    pub fn swap(&mut self, a: ID<T, I, G>, b: ID<T, I, G>) -> bool {
+        let Some((a, b)) = self.get2_mut(a, b) else { return false };
+        core::mem::swap(a, b);
+        true
+    }
+    pub fn contains(&self, id: ID<T, I, G>) -> bool {
+        self.get(id).is_some()
+    }
+    /// Returns true if `a` and `b` are both valid and so refer to the
+    /// same live entry.
+    pub fn same_slot(&self, a: ID<T, I, G>, b: ID<T, I, G>) -> bool {
+        a == b && self.contains(a)
+    }
+    pub fn contains_index(&self, index: usize) -> bool {
+        self.generation_at(index).is_some()
+    }
+    pub fn generation_at(&self, index: usize) -> Option<G> {
+        let Some(Entry::Occupied(gen, _)) = self.entries.get(index) else { return None };
+        Some(G::from_raw(*gen))
+    }
+    /// Returns the current occupant of the slot at `index` along with
+    /// its `ID`, regardless of generation.
+    pub fn get_by_index(&self, index: usize) -> Option<(ID<T, I, G>, &T)> {
+        let Some(Entry::Occupied(gen, item)) = self.entries.get(index) else { return None };
+        Some((ID::new(index, *gen), item))
+    }
+    pub fn get_by_index_mut(&mut self, index: usize) -> Option<(ID<T, I, G>, &mut T)> {
+        let Some(Entry::Occupied(gen, item)) = self.entries.get_mut(index) else { return None };
+        Some((ID::new(index, *gen), item))
+    }
+    /// Returns the `ID` of the occupied entry with the lowest index.
+    pub fn first_id(&self) -> Option<ID<T, I, G>> {
+        self.indices().next()
+    }
+    /// Returns the `ID` of the occupied entry with the highest index.
+    pub fn last_id(&self) -> Option<ID<T, I, G>> {
+        self.indices().next_back()
+    }
+    pub fn first(&self) -> Option<&T> {
+        self.values().next()
+    }
+    pub fn last(&self) -> Option<&T> {
+        self.values().next_back()
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T, I, G> {
         Iter {
             entries: &self.entries,
             index: 0,
+            remaining: self.length,
+        }
+    }
+    /// Like [`Arena::iter`], but also yields the position of every entry
+    /// among the occupied ones, counting from zero without gaps.
+    ///
+    /// Unlike [`ID::index`], the position skips over free slots, so it can
+    /// index into a dense copy of the values.
+    pub fn iter_enumerated(&self) -> IterEnumerated<'_, T, I, G> {
+        IterEnumerated {
+            items: self.iter().enumerate(),
+        }
+    }
+    /// Iterates over the entries from the slot of `start` onwards,
+    /// including `start` itself if it is still occupied.
+    ///
+    /// Only the index of `start` is used, so this also resumes correctly
+    /// after `start` has been removed.
+    pub fn iter_from(&self, start: ID<T, I, G>) -> Iter<'_, T, I, G> {
+        self.iter_range(start.index()..)
+    }
+    /// Iterates over the entries whose slot index lies in `range`.
+    ///
+    /// The range is over slot indices as returned by [`ID::index`], not over
+    /// the position among occupied entries. It is clamped to the slots of
+    /// the arena, so out-of-bounds ranges yield nothing instead of panicking.
+    pub fn iter_range(&self, range: impl RangeBounds<usize>) -> Iter<'_, T, I, G> {
+        let Range { start, end } = self.slot_range(range);
+        let entries = &self.entries[start..end];
+        let remaining = if entries.len() == self.entries.len() {
+            self.length
+        } else {
+            entries.iter().filter(|e| matches!(e, Entry::Occupied(..))).count()
+        };
+        Iter {
+            entries,
+            index: start,
+            remaining,
         }
     }
-    pub fn iter_mut(&mut self) -> IterMut<T> {
+    /// Clamps `range` to the slots of the arena.
+    fn slot_range(&self, range: impl RangeBounds<usize>) -> Range<usize> {
+        let len = self.entries.len();
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start.saturating_add(1),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end.saturating_add(1),
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => len,
+        };
+        let end = end.min(len);
+        start.min(end)..end
+    }
+    /// Iterates mutably over the entries along with their current `ID`s,
+    /// which carry the generation of each slot just like [`Arena::iter`].
+    pub fn iter_mut(&mut self) -> IterMut<'_, T, I, G> {
         IterMut {
             entries: &mut self.entries,
             index: 0,
+            remaining: self.length,
         }
     }
-    pub fn indices(&self) -> Indices<T> {
+    /// Returns a cursor starting at the occupied entry with the lowest index.
+    pub fn cursor_mut(&mut self) -> CursorMut<'_, T, I, G> {
+        CursorMut::new(&mut self.entries)
+    }
+    pub fn indices(&self) -> Indices<'_, T, I, G> {
         let items = self.iter();
         Indices { items }
     }
+    /// Iterates over every slot of the arena in index order, including
+    /// the free ones that [`Arena::iter`] skips.
+    pub fn slots(&self) -> Slots<'_, T, I, G> {
+        Slots {
+            entries: self.entries.iter().enumerate(),
+        }
+    }
+    /// Collects the `ID`s of all entries, in index order.
+    ///
+    /// The `ID`s are not tied to the arena, so entries removed afterwards
+    /// simply make their `ID`s stale. Check them with [`Arena::contains`] or
+    /// look them up with [`Arena::get`] when processing the snapshot.
+    pub fn snapshot_ids(&self) -> Vec<ID<T, I, G>> {
+        let mut ids = Vec::with_capacity(self.len());
+        ids.extend(self.indices());
+        ids
+    }
+    /// Same as [`Arena::indices`].
+    pub fn keys(&self) -> Indices<'_, T, I, G> {
+        self.indices()
+    }
+    pub fn values(&self) -> Values<'_, T, I, G> {
+        let items = self.iter();
+        Values { items }
+    }
+    pub fn values_mut(&mut self) -> ValuesMut<'_, T, I, G> {
+        let items = self.iter_mut();
+        ValuesMut { items }
+    }
+    /// Consumes the arena and yields its values, like `into_iter`.
+    pub fn into_values(self) -> IntoValues<T, I, G, A> {
+        self.into_iter()
+    }
+    /// Consumes the arena and yields every value along with its `ID`.
+    pub fn into_ids_values(self) -> IntoIdsValues<T, I, G, A> {
+        let remaining = self.length;
+        IntoIdsValues {
+            entries: self.entries.into_iter().enumerate(),
+            remaining,
+        }
+    }
+    pub fn drain(&mut self) -> Drain<'_, T, I, G, A> {
+        Drain {
+            arena: self,
+            index: 0,
+        }
+    }
+}
+/// Formats the arena as a map from the `Display` form of each `ID`
+/// to its value, e.g. `Arena {0: 'a', (2-1): 'b'}`.
+impl<T: Debug, I: ArenaIndex, G: ArenaGeneration, A: Allocator> Debug for Arena<T, I, G, A> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("Arena ")?;
+        let mut map = f.debug_map();
+        for (id, t) in self {
+            map.key(&format_args!("{id}")).value(t);
+        }
+        map.finish()
+    }
+}
+impl<T, I, G: ArenaGeneration> Default for Arena<T, I, G> {
+    fn default() -> Self {
+        Self {
+            entries: storage(Vec::new()),
+            free_list_head: None,
+            length: 0,
+            retired: 0,
+            reserved: 0,
+            max_len: None,
+            fresh_generation: G::FIRST,
+            on_grow: None,
+        }
+    }
+}
+impl<T: Clone, I: Clone, G: ArenaGeneration, A: Allocator + Clone> Clone for Arena<T, I, G, A> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            free_list_head: self.free_list_head.clone(),
+            length: self.length,
+            retired: self.retired,
+            reserved: self.reserved,
+            max_len: self.max_len,
+            fresh_generation: self.fresh_generation,
+            on_grow: self.on_grow,
+        }
+    }
+    /// Reuses the allocation of `self`, cloning `source` slot by slot.
+    fn clone_from(&mut self, source: &Self) {
+        self.entries.clone_from(&source.entries);
+        self.free_list_head.clone_from(&source.free_list_head);
+        self.length = source.length;
+        self.retired = source.retired;
+        self.reserved = source.reserved;
+        self.max_len = source.max_len;
+        self.fresh_generation = source.fresh_generation;
+        self.on_grow = source.on_grow;
+    }
 }
-impl<T> Index<ID> for Arena<T> {
+impl<T: PartialEq, I: ArenaIndex, G: ArenaGeneration, A: Allocator> PartialEq for Arena<T, I, G, A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter())
+    }
+}
+impl<T: Eq, I: ArenaIndex, G: ArenaGeneration, A: Allocator> Eq for Arena<T, I, G, A> {}
+/// Hashes the occupied entries in ascending index order, consistent with `PartialEq`.
+impl<T: Hash, I: ArenaIndex, G: ArenaGeneration, A: Allocator> Hash for Arena<T, I, G, A> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        for (id, t) in self {
+            id.hash(state);
+            t.hash(state);
+        }
+    }
+}
+impl<T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> Index<ID<T, I, G>> for Arena<T, I, G, A> {
     type Output = T;
-    fn index(&self, index: ID) -> &Self::Output {
-        self.get(index).unwrap()
+    fn index(&self, index: ID<T, I, G>) -> &Self::Output {
+        match self.try_get(index) {
+            Ok(item) => item,
+            Err(err) => panic!("invalid arena ID {index}: {err}"),
+        }
+    }
+}
+impl<T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> IndexMut<ID<T, I, G>> for Arena<T, I, G, A> {
+    fn index_mut(&mut self, index: ID<T, I, G>) -> &mut Self::Output {
+        match self.try_get_mut(index) {
+            Ok(item) => item,
+            Err(err) => panic!("invalid arena ID {index}: {err}"),
+        }
+    }
+}
+impl<T, I: ArenaIndex, G: ArenaGeneration> FromIterator<T> for Arena<T, I, G> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        let mut arena = Self::default();
+        arena.extend(iter);
+        arena
     }
 }
-impl<T> IndexMut<ID> for Arena<T> {
-    fn index_mut(&mut self, index: ID) -> &mut Self::Output {
-        self.get_mut(index).unwrap()
+impl<T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> Extend<T> for Arena<T, I, G, A> {
+    fn extend<It: IntoIterator<Item = T>>(&mut self, iter: It) {
+        let iter = iter.into_iter();
+        self.reserve_for_insert(iter.size_hint().0);
+        for t in iter {
+            self.insert(t);
+        }
     }
 }
-impl<T> IntoIterator for Arena<T> {
-    type IntoIter = IntoIter<T>;
+impl<T, I, G: ArenaGeneration, A: Allocator> IntoIterator for Arena<T, I, G, A> {
+    type IntoIter = IntoIter<T, I, G, A>;
     type Item = T;
     fn into_iter(self) -> Self::IntoIter {
+        let remaining = self.length;
         let entries = self.entries.into_iter();
-        IntoIter { entries }
+        IntoIter { entries, remaining }
     }
 }
-impl<'a, T> IntoIterator for &'a Arena<T> {
-    type IntoIter = Iter<'a, T>;
-    type Item = (ID, &'a T);
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> IntoIterator for &'a Arena<T, I, G, A> {
+    type IntoIter = Iter<'a, T, I, G>;
+    type Item = (ID<T, I, G>, &'a T);
     fn into_iter(self) -> Self::IntoIter {
         self.iter()
     }
 }
-impl<'a, T> IntoIterator for &'a mut Arena<T> {
-    type IntoIter = IterMut<'a, T>;
-    type Item = (ID, &'a mut T);
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> IntoIterator for &'a mut Arena<T, I, G, A> {
+    type IntoIter = IterMut<'a, T, I, G>;
+    type Item = (ID<T, I, G>, &'a mut T);
     fn into_iter(self) -> Self::IntoIter {
         self.iter_mut()
     }
 }
 
+/// Looks up one entry in each of two arenas at once.
+///
+/// Returns `None` unless both `ID`s are valid in their arena. The typed
+/// `ID`s make it a compile error to mix up which `ID` goes with which arena.
+pub fn get_pair<'a, T, U, I: ArenaIndex, G: ArenaGeneration>(
+    a: &'a Arena<T, I, G>,
+    id_a: ID<T, I, G>,
+    b: &'a Arena<U, I, G>,
+    id_b: ID<U, I, G>,
+) -> Option<(&'a T, &'a U)> {
+    Some((a.get(id_a)?, b.get(id_b)?))
+}
+/// Mutable version of [`get_pair`].
+///
+/// The two arenas have to be distinct, which the borrow checker already
+/// enforces, so unlike [`Arena::get2_mut`] this needs no aliasing check.
+pub fn get_pair_mut<'a, T, U, I: ArenaIndex, G: ArenaGeneration>(
+    a: &'a mut Arena<T, I, G>,
+    id_a: ID<T, I, G>,
+    b: &'a mut Arena<U, I, G>,
+    id_b: ID<U, I, G>,
+) -> Option<(&'a mut T, &'a mut U)> {
+    Some((a.get_mut(id_a)?, b.get_mut(id_b)?))
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ArenaStats {
+    pub len: usize,
+    pub free: usize,
+    pub capacity: usize,
+    pub highest_index: Option<usize>,
+}
+
+/// The differences between two states of an arena, as returned by [`Arena::diff`].
+pub struct ArenaDiff<'a, T, I = usize, G: ArenaGeneration = Generation> {
+    /// The `ID`s only valid in the newer arena.
+    pub added: Vec<ID<T, I, G>>,
+    /// The `ID`s only valid in the older arena.
+    pub removed: Vec<ID<T, I, G>>,
+    /// The `ID`s valid in both arenas whose value changed, along with
+    /// the old and the new value.
+    pub changed: Vec<(ID<T, I, G>, &'a T, &'a T)>,
+}
+impl<T, I, G: ArenaGeneration> ArenaDiff<'_, T, I, G> {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+impl<T: Debug, I: ArenaIndex, G: ArenaGeneration> Debug for ArenaDiff<'_, T, I, G> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ArenaDiff")
+            .field("added", &self.added)
+            .field("removed", &self.removed)
+            .field("changed", &self.changed)
+            .finish()
+    }
+}
+
+/// A saved state of an arena, created by [`Arena::checkpoint`].
+#[derive(Clone)]
+pub struct Checkpoint<T, I = usize, G: ArenaGeneration = Generation, A: Allocator = Global> {
+    arena: Arena<T, I, G, A>,
+}
+
+/// A slot of an arena, as accepted by [`Arena::from_entries`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RawEntry<T, G = Generation> {
+    Occupied { generation: G, value: T },
+    Free { next_generation: G, next_free: Option<usize> },
+    Reserved { generation: G },
+    Retired,
+}
+
+/// A read-only view of a slot of an arena, as returned by [`Arena::slots`].
+pub enum Slot<'a, T, I = usize, G: ArenaGeneration = Generation> {
+    Occupied { id: ID<T, I, G>, value: &'a T },
+    Free { index: usize },
+    Reserved { id: ID<T, I, G> },
+    Retired { index: usize },
+}
+impl<T, I: ArenaIndex, G: ArenaGeneration> Slot<'_, T, I, G> {
+    pub fn index(&self) -> usize {
+        match self {
+            Slot::Occupied { id, .. } | Slot::Reserved { id } => id.index(),
+            Slot::Free { index } | Slot::Retired { index } => *index,
+        }
+    }
+}
+impl<T, I: ArenaIndex, G: ArenaGeneration> Clone for Slot<'_, T, I, G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl<T, I: ArenaIndex, G: ArenaGeneration> Copy for Slot<'_, T, I, G> {}
+impl<T: Debug, I: ArenaIndex, G: ArenaGeneration> Debug for Slot<'_, T, I, G> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Slot::Occupied { id, value } => f
+                .debug_struct("Occupied")
+                .field("id", id)
+                .field("value", value)
+                .finish(),
+            Slot::Free { index } => f.debug_struct("Free").field("index", index).finish(),
+            Slot::Reserved { id } => f.debug_struct("Reserved").field("id", id).finish(),
+            Slot::Retired { index } => f.debug_struct("Retired").field("index", index).finish(),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug)]
-enum Entry<T> {
+enum Entry<T, I, G: ArenaGeneration> {
     Free {
-        next_generation: Generation,
-        next_free: Option<usize>,
+        next_generation: G::Raw,
+        next_free: Option<I>,
     },
-    Occupied(Generation, T),
+    Occupied(G::Raw, T),
+    Retired,
+    Reserved(G::Raw),
+}
+impl<T, I, G: ArenaGeneration> Entry<T, I, G> {
+    fn as_ref(&self) -> Entry<&T, I, G>
+    where
+        I: Copy,
+    {
+        match self {
+            Entry::Free {
+                next_generation,
+                next_free,
+            } => Entry::Free {
+                next_generation: *next_generation,
+                next_free: *next_free,
+            },
+            Entry::Occupied(gen, t) => Entry::Occupied(*gen, t),
+            Entry::Reserved(gen) => Entry::Reserved(*gen),
+            Entry::Retired => Entry::Retired,
+        }
+    }
+    fn map<U, F>(self, f: F) -> Entry<U, I, G>
+    where
+        F: FnOnce(G::Raw, T) -> U,
+    {
+        match self {
+            Entry::Free {
+                next_generation,
+                next_free,
+            } => Entry::Free {
+                next_generation,
+                next_free,
+            },
+            Entry::Occupied(gen, t) => Entry::Occupied(gen, f(gen, t)),
+            Entry::Reserved(gen) => Entry::Reserved(gen),
+            Entry::Retired => Entry::Retired,
+        }
+    }
 }
 
-pub struct Iter<'a, T> {
-    entries: &'a [Entry<T>],
+pub struct Iter<'a, T, I = usize, G: ArenaGeneration = Generation> {
+    entries: &'a [Entry<T, I, G>],
     index: usize,
+    remaining: usize,
 }
-impl<'a, T> Iterator for Iter<'a, T> {
-    type Item = (ID, &'a T);
+impl<T, I, G: ArenaGeneration> Clone for Iter<'_, T, I, G> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries,
+            index: self.index,
+            remaining: self.remaining,
+        }
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> Iterator for Iter<'a, T, I, G> {
+    type Item = (ID<T, I, G>, &'a T);
     fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
         loop {
-            match replace(&mut self.entries, &[]) {
+            match take(&mut self.entries) {
                 [] => return None,
                 [first, rest @ ..] => {
                     self.entries = rest;
@@ -175,38 +1906,102 @@ impl<'a, T> Iterator for Iter<'a, T> {
 
                     if let Entry::Occupied(gen, t) = first {
                         let id = ID::new(index, *gen);
+                        self.remaining -= 1;
                         return Some((id, t));
                     }
                 }
             }
         }
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+    fn count(self) -> usize {
+        self.remaining
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let Some(skipped) = nth_occupied(self.entries, n) else {
+            self.entries = &[];
+            self.remaining = 0;
+            return None;
+        };
+
+        self.entries = &self.entries[skipped..];
+        self.index += skipped;
+        self.remaining -= n;
+        self.next()
+    }
 }
-impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> DoubleEndedIterator for Iter<'a, T, I, G> {
     fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
         loop {
-            let entries = replace(&mut self.entries, &[]);
+            let entries = take(&mut self.entries);
             let (last, others) = entries.split_last()?;
             let index = self.index + others.len();
             self.entries = others;
 
             if let Entry::Occupied(gen, t) = last {
                 let id = ID::new(index, *gen);
+                self.remaining -= 1;
                 return Some((id, t));
             }
         }
     }
 }
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> ExactSizeIterator for Iter<'a, T, I, G> {}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> FusedIterator for Iter<'a, T, I, G> {}
+
+pub struct IterEnumerated<'a, T, I = usize, G: ArenaGeneration = Generation> {
+    items: core::iter::Enumerate<Iter<'a, T, I, G>>,
+}
+impl<T, I, G: ArenaGeneration> Clone for IterEnumerated<'_, T, I, G> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+        }
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> Iterator for IterEnumerated<'a, T, I, G> {
+    type Item = (usize, ID<T, I, G>, &'a T);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next().map(|(n, (id, t))| (n, id, t))
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.items.nth(n).map(|(n, (id, t))| (n, id, t))
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+    fn count(self) -> usize {
+        self.items.count()
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> DoubleEndedIterator for IterEnumerated<'a, T, I, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.items.next_back().map(|(n, (id, t))| (n, id, t))
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> ExactSizeIterator for IterEnumerated<'a, T, I, G> {}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> FusedIterator for IterEnumerated<'a, T, I, G> {}
 
-pub struct IterMut<'a, T> {
-    entries: &'a mut [Entry<T>],
+pub struct IterMut<'a, T, I = usize, G: ArenaGeneration = Generation> {
+    entries: &'a mut [Entry<T, I, G>],
     index: usize,
+    remaining: usize,
 }
-impl<'a, T> Iterator for IterMut<'a, T> {
-    type Item = (ID, &'a mut T);
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> Iterator for IterMut<'a, T, I, G> {
+    type Item = (ID<T, I, G>, &'a mut T);
     fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
         loop {
-            match replace(&mut self.entries, &mut []) {
+            match take(&mut self.entries) {
                 [] => return None,
                 [first, rest @ ..] => {
                     self.entries = rest;
@@ -215,88 +2010,434 @@ impl<'a, T> Iterator for IterMut<'a, T> {
 
                     if let Entry::Occupied(gen, t) = first {
                         let id = ID::new(index, *gen);
+                        self.remaining -= 1;
                         return Some((id, t));
                     }
                 }
             }
         }
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+    fn count(self) -> usize {
+        self.remaining
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let Some(skipped) = nth_occupied(self.entries, n) else {
+            self.entries = &mut [];
+            self.remaining = 0;
+            return None;
+        };
+
+        self.entries = &mut take(&mut self.entries)[skipped..];
+        self.index += skipped;
+        self.remaining -= n;
+        self.next()
+    }
 }
-impl<'a, T> DoubleEndedIterator for IterMut<'a, T> {
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> DoubleEndedIterator for IterMut<'a, T, I, G> {
     fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
         loop {
-            let entries = replace(&mut self.entries, &mut []);
+            let entries = take(&mut self.entries);
             let (last, others) = entries.split_last_mut()?;
             let index = self.index + others.len();
             self.entries = others;
 
             if let Entry::Occupied(gen, t) = last {
                 let id = ID::new(index, *gen);
+                self.remaining -= 1;
                 return Some((id, t));
             }
         }
     }
 }
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> ExactSizeIterator for IterMut<'a, T, I, G> {}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> FusedIterator for IterMut<'a, T, I, G> {}
 
-pub struct IntoIter<T> {
-    entries: std::vec::IntoIter<Entry<T>>,
+pub struct IntoIter<T, I = usize, G: ArenaGeneration = Generation, A: Allocator = Global> {
+    entries: StorageIntoIter<Entry<T, I, G>, A>,
+    remaining: usize,
 }
-impl<T> Iterator for IntoIter<T> {
+impl<T, I, G: ArenaGeneration, A: Allocator> Iterator for IntoIter<T, I, G, A> {
     type Item = T;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
         loop {
             let entry = self.entries.next()?;
             if let Entry::Occupied(_, t) = entry {
+                self.remaining -= 1;
                 return Some(t);
             }
         }
     }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+    fn count(self) -> usize {
+        self.remaining
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        let Some(skipped) = nth_occupied(self.entries.as_slice(), n) else {
+            self.entries.by_ref().for_each(drop);
+            self.remaining = 0;
+            return None;
+        };
+
+        let Some(Entry::Occupied(_, t)) = self.entries.nth(skipped) else { unreachable!() };
+        self.remaining -= n + 1;
+        Some(t)
+    }
 }
-impl<T> DoubleEndedIterator for IntoIter<T> {
+impl<T, I, G: ArenaGeneration, A: Allocator> DoubleEndedIterator for IntoIter<T, I, G, A> {
     fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
         loop {
             let entry = self.entries.next_back()?;
             if let Entry::Occupied(_, t) = entry {
+                self.remaining -= 1;
                 return Some(t);
             }
         }
     }
 }
+impl<T, I, G: ArenaGeneration, A: Allocator> ExactSizeIterator for IntoIter<T, I, G, A> {}
+impl<T, I, G: ArenaGeneration, A: Allocator> FusedIterator for IntoIter<T, I, G, A> {}
+
+pub type IntoValues<T, I = usize, G = Generation, A = Global> = IntoIter<T, I, G, A>;
 
-pub struct Indices<'a, T> {
-    items: Iter<'a, T>,
+pub struct IntoIdsValues<T, I = usize, G: ArenaGeneration = Generation, A: Allocator = Global> {
+    entries: core::iter::Enumerate<StorageIntoIter<Entry<T, I, G>, A>>,
+    remaining: usize,
 }
-impl<'a, T> Iterator for Indices<'a, T> {
-    type Item = ID;
+impl<T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> Iterator for IntoIdsValues<T, I, G, A> {
+    type Item = (ID<T, I, G>, T);
+
     fn next(&mut self) -> Option<Self::Item> {
-        self.items.next().map(|(i, _)| i)
+        if self.remaining == 0 {
+            return None;
+        }
+
+        loop {
+            let (index, entry) = self.entries.next()?;
+            if let Entry::Occupied(gen, t) = entry {
+                self.remaining -= 1;
+                return Some((ID::new(index, gen), t));
+            }
+        }
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+    fn count(self) -> usize {
+        self.remaining
+    }
+}
+impl<T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> DoubleEndedIterator for IntoIdsValues<T, I, G, A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        loop {
+            let (index, entry) = self.entries.next_back()?;
+            if let Entry::Occupied(gen, t) = entry {
+                self.remaining -= 1;
+                return Some((ID::new(index, gen), t));
+            }
+        }
+    }
+}
+impl<T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> ExactSizeIterator for IntoIdsValues<T, I, G, A> {}
+impl<T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> FusedIterator for IntoIdsValues<T, I, G, A> {}
+
+pub struct Values<'a, T, I = usize, G: ArenaGeneration = Generation> {
+    items: Iter<'a, T, I, G>,
+}
+impl<T, I, G: ArenaGeneration> Clone for Values<'_, T, I, G> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+        }
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> Iterator for Values<'a, T, I, G> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next().map(|(_, t)| t)
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.items.nth(n).map(|(_, t)| t)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+    fn count(self) -> usize {
+        self.items.count()
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> DoubleEndedIterator for Values<'a, T, I, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.items.next_back().map(|(_, t)| t)
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> ExactSizeIterator for Values<'a, T, I, G> {}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> FusedIterator for Values<'a, T, I, G> {}
+
+pub struct ValuesMut<'a, T, I = usize, G: ArenaGeneration = Generation> {
+    items: IterMut<'a, T, I, G>,
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> Iterator for ValuesMut<'a, T, I, G> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next().map(|(_, t)| t)
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.items.nth(n).map(|(_, t)| t)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+    fn count(self) -> usize {
+        self.items.count()
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> DoubleEndedIterator for ValuesMut<'a, T, I, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.items.next_back().map(|(_, t)| t)
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> ExactSizeIterator for ValuesMut<'a, T, I, G> {}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> FusedIterator for ValuesMut<'a, T, I, G> {}
+
+pub struct Drain<'a, T, I: ArenaIndex = usize, G: ArenaGeneration = Generation, A: Allocator = Global> {
+    arena: &'a mut Arena<T, I, G, A>,
+    index: usize,
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> Iterator for Drain<'a, T, I, G, A> {
+    type Item = (ID<T, I, G>, T);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let index = self.index;
+            let entry = self.arena.entries.get(index)?;
+            self.index += 1;
+
+            if let &Entry::Occupied(gen, _) = entry {
+                let id = ID::new(index, gen);
+                let item = self.arena.remove(id)?;
+                return Some((id, item));
+            }
+        }
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> Drop for Drain<'a, T, I, G, A> {
+    fn drop(&mut self) {
+        self.for_each(drop);
+        self.arena.rebuild_free_list();
     }
 }
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
-pub struct ID {
+pub struct ExtractIf<'a, T, I, G, F, A = Global>
+where
+    I: ArenaIndex,
+    G: ArenaGeneration,
+    F: FnMut(ID<T, I, G>, &mut T) -> bool,
+    A: Allocator,
+{
+    arena: &'a mut Arena<T, I, G, A>,
     index: usize,
-    generation: Generation,
+    removed: bool,
+    /// Set while `f` runs, so that a panicking `f` is not called again
+    /// from `drop` during unwinding.
+    in_predicate: bool,
+    f: F,
+}
+impl<'a, T, I, G, F, A> Iterator for ExtractIf<'a, T, I, G, F, A>
+where
+    I: ArenaIndex,
+    G: ArenaGeneration,
+    F: FnMut(ID<T, I, G>, &mut T) -> bool,
+    A: Allocator,
+{
+    type Item = (ID<T, I, G>, T);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let index = self.index;
+            let entry = self.arena.entries.get_mut(index)?;
+            self.index += 1;
+
+            if let Entry::Occupied(gen, t) = entry {
+                let id = ID::new(index, *gen);
+                self.in_predicate = true;
+                let extract = (self.f)(id, t);
+                self.in_predicate = false;
+                if extract {
+                    let item = self.arena.remove(id)?;
+                    self.removed = true;
+                    return Some((id, item));
+                }
+            }
+        }
+    }
 }
-impl ID {
-    fn new(index: usize, generation: Generation) -> Self {
-        Self { index, generation }
+impl<'a, T, I, G, F, A> Drop for ExtractIf<'a, T, I, G, F, A>
+where
+    I: ArenaIndex,
+    G: ArenaGeneration,
+    F: FnMut(ID<T, I, G>, &mut T) -> bool,
+    A: Allocator,
+{
+    fn drop(&mut self) {
+        if !self.in_predicate {
+            self.for_each(drop);
+        }
+        if self.removed {
+            self.arena.rebuild_free_list();
+        }
     }
+}
 
-    pub fn index(&self) -> usize {
-        self.index
+pub struct Indices<'a, T, I = usize, G: ArenaGeneration = Generation> {
+    items: Iter<'a, T, I, G>,
+}
+impl<T, I, G: ArenaGeneration> Clone for Indices<'_, T, I, G> {
+    fn clone(&self) -> Self {
+        Self {
+            items: self.items.clone(),
+        }
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> Iterator for Indices<'a, T, I, G> {
+    type Item = ID<T, I, G>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.items.next().map(|(i, _)| i)
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.items.nth(n).map(|(i, _)| i)
     }
-    pub fn generation(&self) -> Generation {
-        self.generation
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.items.size_hint()
+    }
+    fn count(self) -> usize {
+        self.items.count()
     }
 }
-impl Display for ID {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        if self.generation == 0 {
-            write!(f, "{}", self.index)
-        } else {
-            write!(f, "({}-{})", self.index, self.generation)
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> DoubleEndedIterator for Indices<'a, T, I, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.items.next_back().map(|(i, _)| i)
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> ExactSizeIterator for Indices<'a, T, I, G> {}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> FusedIterator for Indices<'a, T, I, G> {}
+
+pub struct Slots<'a, T, I = usize, G: ArenaGeneration = Generation> {
+    entries: core::iter::Enumerate<core::slice::Iter<'a, Entry<T, I, G>>>,
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> Slots<'a, T, I, G> {
+    fn slot((index, entry): (usize, &'a Entry<T, I, G>)) -> Slot<'a, T, I, G> {
+        match entry {
+            Entry::Occupied(gen, value) => Slot::Occupied {
+                id: ID::new(index, *gen),
+                value,
+            },
+            Entry::Free { .. } => Slot::Free { index },
+            Entry::Reserved(gen) => Slot::Reserved {
+                id: ID::new(index, *gen),
+            },
+            Entry::Retired => Slot::Retired { index },
+        }
+    }
+}
+impl<T, I, G: ArenaGeneration> Clone for Slots<'_, T, I, G> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+        }
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> Iterator for Slots<'a, T, I, G> {
+    type Item = Slot<'a, T, I, G>;
+    fn next(&mut self) -> Option<Self::Item> {
+        self.entries.next().map(Self::slot)
+    }
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.entries.nth(n).map(Self::slot)
+    }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.entries.size_hint()
+    }
+    fn count(self) -> usize {
+        self.entries.count()
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> DoubleEndedIterator for Slots<'a, T, I, G> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.entries.next_back().map(Self::slot)
+    }
+}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> ExactSizeIterator for Slots<'a, T, I, G> {}
+impl<'a, T, I: ArenaIndex, G: ArenaGeneration> FusedIterator for Slots<'a, T, I, G> {}
+
+/// Returns the position of the `n`th occupied entry in `entries`.
+fn nth_occupied<T, I, G: ArenaGeneration>(entries: &[Entry<T, I, G>], n: usize) -> Option<usize> {
+    entries
+        .iter()
+        .enumerate()
+        .filter(|(_, entry)| matches!(entry, Entry::Occupied(..)))
+        .nth(n)
+        .map(|(index, _)| index)
+}
+
+fn find_free_list_head<T, I: ArenaIndex, G: ArenaGeneration>(
+    entries: &[Entry<T, I, G>],
+) -> Result<Option<I>, FromEntriesError> {
+    let mut referenced = vec![false; entries.len()];
+    let mut free = 0;
+
+    for entry in entries {
+        let Entry::Free { next_free, .. } = entry else { continue };
+        free += 1;
+
+        let Some(next) = next_free.map(I::to_usize) else { continue };
+        match entries.get(next) {
+            Some(Entry::Free { .. }) if !referenced[next] => referenced[next] = true,
+            Some(Entry::Free { .. }) => return Err(FromEntriesError::LinkedTwice),
+            Some(_) => return Err(FromEntriesError::LinkToNonFree),
+            None => return Err(FromEntriesError::LinkOutOfBounds),
         }
     }
+
+    let mut heads = entries
+        .iter()
+        .enumerate()
+        .filter(|&(index, entry)| matches!(entry, Entry::Free { .. }) && !referenced[index])
+        .map(|(index, _)| index);
+    let head = heads.next();
+    if heads.next().is_some() {
+        return Err(FromEntriesError::NotSingleChain);
+    }
+
+    let mut visited = 0;
+    let mut current = head;
+    while let Some(index) = current {
+        let Entry::Free { next_free, .. } = entries[index] else { unreachable!() };
+        visited += 1;
+        current = next_free.map(I::to_usize);
+    }
+    if visited != free {
+        return Err(FromEntriesError::Cycle);
+    }
+
+    Ok(head.map(I::from_usize))
 }
@@ -0,0 +1,35 @@
+use crate::{Allocator, Arena, ArenaGeneration, ArenaIndex, Entry, ID};
+use rayon::prelude::*;
+
+impl<T, I: ArenaIndex, G: ArenaGeneration, A: Allocator> Arena<T, I, G, A> {
+    /// Iterates over every entry in parallel.
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = (ID<T, I, G>, &T)>
+    where
+        T: Sync,
+        I: Send + Sync,
+        G::Raw: Send + Sync,
+    {
+        self.entries
+            .par_iter()
+            .enumerate()
+            .filter_map(|(index, entry)| match entry {
+                Entry::Occupied(gen, item) => Some((ID::new(index, *gen), item)),
+                _ => None,
+            })
+    }
+    /// Iterates over every entry in parallel, with mutable access to the values.
+    pub fn par_iter_mut(&mut self) -> impl ParallelIterator<Item = (ID<T, I, G>, &mut T)>
+    where
+        T: Send,
+        I: Send,
+        G::Raw: Send,
+    {
+        self.entries
+            .par_iter_mut()
+            .enumerate()
+            .filter_map(|(index, entry)| match entry {
+                Entry::Occupied(gen, item) => Some((ID::new(index, *gen), item)),
+                _ => None,
+            })
+    }
+}
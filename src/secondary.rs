@@ -0,0 +1,206 @@
+use crate::{ArenaGeneration, ArenaIndex, Generation, ID};
+use alloc::vec::Vec;
+use core::{
+    fmt::Debug,
+    iter::Enumerate,
+    marker::PhantomData,
+    ops::{Index, IndexMut},
+    slice,
+};
+
+type Slot<V, G> = Option<(<G as ArenaGeneration>::Raw, V)>;
+
+/// Associates extra values with the IDs of an `Arena<M>`.
+///
+/// Values are stored densely by slot index, so lookups are as cheap as
+/// in the arena itself. Each value remembers the generation of the `ID`
+/// it was inserted with, so a stale `ID` never sees the value of a newer
+/// entry that happens to reuse its slot.
+pub struct SecondaryMap<M, V, I = usize, G: ArenaGeneration = Generation> {
+    slots: Vec<Slot<V, G>>,
+    length: usize,
+    marker: PhantomData<fn() -> (M, I)>,
+}
+impl<M, V, I: ArenaIndex, G: ArenaGeneration> SecondaryMap<M, V, I, G> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.length
+    }
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Associates `value` with `id`, returning the value previously
+    /// associated with `id`.
+    ///
+    /// A value stored for an older generation of the same slot is replaced
+    /// and dropped, as its `ID` is stale. A value stored for a newer
+    /// generation is kept instead, and `value` is handed back as `Err`.
+    ///
+    /// Panics if `id` is [`ID::NULL`] or its index is too large to allocate
+    /// slots up to it, leaving the map unchanged.
+    pub fn insert(&mut self, id: ID<M, I, G>, value: V) -> Result<Option<V>, V> {
+        assert!(!id.is_null(), "ID::NULL can't be inserted into a secondary map");
+        let index = id.index();
+        if index >= self.slots.len() {
+            let len = index.checked_add(1).expect("secondary map index overflow");
+            self.slots
+                .try_reserve(len - self.slots.len())
+                .expect("secondary map index too large to allocate");
+            self.slots.resize_with(len, || None);
+        }
+
+        let slot = &mut self.slots[index];
+        match slot {
+            Some((gen, _)) if *gen > id.generation => Err(value),
+            Some((gen, old)) if *gen == id.generation => Ok(Some(core::mem::replace(old, value))),
+            Some(_) => {
+                *slot = Some((id.generation, value));
+                Ok(None)
+            }
+            None => {
+                *slot = Some((id.generation, value));
+                self.length += 1;
+                Ok(None)
+            }
+        }
+    }
+    pub fn remove(&mut self, id: ID<M, I, G>) -> Option<V> {
+        if !self.contains_key(id) {
+            return None;
+        }
+
+        let (_, value) = self.slots[id.index()].take()?;
+        self.length -= 1;
+        Some(value)
+    }
+    pub fn clear(&mut self) {
+        self.slots.clear();
+        self.length = 0;
+    }
+
+    pub fn get(&self, id: ID<M, I, G>) -> Option<&V> {
+        let (gen, value) = self.slots.get(id.index())?.as_ref()?;
+
+        if id.generation != *gen {
+            None
+        } else {
+            Some(value)
+        }
+    }
+    pub fn get_mut(&mut self, id: ID<M, I, G>) -> Option<&mut V> {
+        let (gen, value) = self.slots.get_mut(id.index())?.as_mut()?;
+
+        if id.generation != *gen {
+            None
+        } else {
+            Some(value)
+        }
+    }
+    pub fn contains_key(&self, id: ID<M, I, G>) -> bool {
+        self.get(id).is_some()
+    }
+
+    pub fn iter(&self) -> Iter<'_, M, V, I, G> {
+        Iter {
+            slots: self.slots.iter().enumerate(),
+            marker: PhantomData,
+        }
+    }
+    pub fn iter_mut(&mut self) -> IterMut<'_, M, V, I, G> {
+        IterMut {
+            slots: self.slots.iter_mut().enumerate(),
+            marker: PhantomData,
+        }
+    }
+}
+impl<M, V: Clone, I, G: ArenaGeneration> Clone for SecondaryMap<M, V, I, G> {
+    fn clone(&self) -> Self {
+        Self {
+            slots: self.slots.clone(),
+            length: self.length,
+            marker: PhantomData,
+        }
+    }
+    fn clone_from(&mut self, source: &Self) {
+        self.slots.clone_from(&source.slots);
+        self.length = source.length;
+    }
+}
+/// Formats the map like an [`Arena`](crate::Arena), keyed by the `Display`
+/// form of each `ID`.
+impl<M, V: Debug, I: ArenaIndex, G: ArenaGeneration> Debug for SecondaryMap<M, V, I, G> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str("SecondaryMap ")?;
+        let mut map = f.debug_map();
+        for (id, value) in self {
+            map.key(&format_args!("{id}")).value(value);
+        }
+        map.finish()
+    }
+}
+impl<M, V, I, G: ArenaGeneration> Default for SecondaryMap<M, V, I, G> {
+    fn default() -> Self {
+        Self {
+            slots: Vec::new(),
+            length: 0,
+            marker: PhantomData,
+        }
+    }
+}
+impl<M, V, I: ArenaIndex, G: ArenaGeneration> Index<ID<M, I, G>> for SecondaryMap<M, V, I, G> {
+    type Output = V;
+    fn index(&self, index: ID<M, I, G>) -> &Self::Output {
+        self.get(index).unwrap()
+    }
+}
+impl<M, V, I: ArenaIndex, G: ArenaGeneration> IndexMut<ID<M, I, G>> for SecondaryMap<M, V, I, G> {
+    fn index_mut(&mut self, index: ID<M, I, G>) -> &mut Self::Output {
+        self.get_mut(index).unwrap()
+    }
+}
+impl<'a, M, V, I: ArenaIndex, G: ArenaGeneration> IntoIterator for &'a SecondaryMap<M, V, I, G> {
+    type IntoIter = Iter<'a, M, V, I, G>;
+    type Item = (ID<M, I, G>, &'a V);
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+impl<'a, M, V, I: ArenaIndex, G: ArenaGeneration> IntoIterator for &'a mut SecondaryMap<M, V, I, G> {
+    type IntoIter = IterMut<'a, M, V, I, G>;
+    type Item = (ID<M, I, G>, &'a mut V);
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter_mut()
+    }
+}
+
+pub struct Iter<'a, M, V, I = usize, G: ArenaGeneration = Generation> {
+    slots: Enumerate<slice::Iter<'a, Slot<V, G>>>,
+    marker: PhantomData<fn() -> (M, I)>,
+}
+impl<'a, M, V, I: ArenaIndex, G: ArenaGeneration> Iterator for Iter<'a, M, V, I, G> {
+    type Item = (ID<M, I, G>, &'a V);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.find_map(|(index, slot)| {
+            let (gen, value) = slot.as_ref()?;
+            Some((ID::new(index, *gen), value))
+        })
+    }
+}
+
+pub struct IterMut<'a, M, V, I = usize, G: ArenaGeneration = Generation> {
+    slots: Enumerate<slice::IterMut<'a, Slot<V, G>>>,
+    marker: PhantomData<fn() -> (M, I)>,
+}
+impl<'a, M, V, I: ArenaIndex, G: ArenaGeneration> Iterator for IterMut<'a, M, V, I, G> {
+    type Item = (ID<M, I, G>, &'a mut V);
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.find_map(|(index, slot)| {
+            let (gen, value) = slot.as_mut()?;
+            Some((ID::new(index, *gen), value))
+        })
+    }
+}
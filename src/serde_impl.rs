@@ -0,0 +1,152 @@
+use crate::{Arena, ArenaGeneration, ArenaIndex, Entry, FromEntriesError, ID};
+use alloc::vec::Vec;
+use core::{fmt::Formatter, marker::PhantomData};
+use serde::{
+    de::{Error, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+/// The serialized form of a slot. It mirrors `Entry`, but stores logical
+/// generations instead of their offset non-zero representation, so that a
+/// slot's generation reads the same as the generation of its `ID`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "Entry")]
+enum EntryData<T, I, G> {
+    Free { next_generation: G, next_free: Option<I> },
+    Occupied(G, T),
+    Retired,
+    Reserved(G),
+}
+impl<T, I, G: ArenaGeneration> EntryData<T, I, G> {
+    fn from_entry(entry: &Entry<T, I, G>) -> EntryData<&T, I, G>
+    where
+        I: Copy,
+    {
+        match entry {
+            Entry::Free {
+                next_generation,
+                next_free,
+            } => EntryData::Free {
+                next_generation: G::from_raw(*next_generation),
+                next_free: *next_free,
+            },
+            Entry::Occupied(gen, t) => EntryData::Occupied(G::from_raw(*gen), t),
+            Entry::Retired => EntryData::Retired,
+            Entry::Reserved(gen) => EntryData::Reserved(G::from_raw(*gen)),
+        }
+    }
+    fn into_entry<E: Error>(self) -> Result<Entry<T, I, G>, E> {
+        let to_raw = |gen: G| gen.to_raw().ok_or_else(|| E::custom(FromEntriesError::GenerationOverflow));
+        Ok(match self {
+            EntryData::Free {
+                next_generation,
+                next_free,
+            } => Entry::Free {
+                next_generation: to_raw(next_generation)?,
+                next_free,
+            },
+            EntryData::Occupied(gen, t) => Entry::Occupied(to_raw(gen)?, t),
+            EntryData::Retired => Entry::Retired,
+            EntryData::Reserved(gen) => Entry::Reserved(to_raw(gen)?),
+        })
+    }
+}
+
+struct EntriesRef<'a, T, I, G: ArenaGeneration>(&'a [Entry<T, I, G>]);
+impl<T: Serialize, I: ArenaIndex + Serialize, G: ArenaGeneration + Serialize> Serialize for EntriesRef<'_, T, I, G> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(EntryData::from_entry))
+    }
+}
+
+#[derive(Serialize)]
+#[serde(bound = "T: Serialize, I: ArenaIndex + Serialize, G: ArenaGeneration + Serialize")]
+struct ArenaRef<'a, T, I, G: ArenaGeneration> {
+    entries: EntriesRef<'a, T, I, G>,
+    max_len: Option<usize>,
+    fresh_generation: G,
+}
+
+#[derive(Deserialize)]
+#[serde(bound = "T: Deserialize<'de>, I: Deserialize<'de>, G: Deserialize<'de>")]
+struct ArenaData<T, I, G> {
+    entries: Vec<EntryData<T, I, G>>,
+    max_len: Option<usize>,
+    fresh_generation: G,
+}
+
+impl<T, I, G> Serialize for Arena<T, I, G>
+where
+    T: Serialize,
+    I: ArenaIndex + Serialize,
+    G: ArenaGeneration + Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        ArenaRef {
+            entries: EntriesRef(&self.entries),
+            max_len: self.max_len,
+            fresh_generation: G::from_raw(self.fresh_generation),
+        }
+        .serialize(serializer)
+    }
+}
+impl<'de, T, I, G> Deserialize<'de> for Arena<T, I, G>
+where
+    T: Deserialize<'de>,
+    I: ArenaIndex + Deserialize<'de>,
+    G: ArenaGeneration + Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let ArenaData {
+            entries,
+            max_len,
+            fresh_generation,
+        } = ArenaData::<T, I, G>::deserialize(deserializer)?;
+        let entries = entries
+            .into_iter()
+            .map(EntryData::into_entry)
+            .collect::<Result<Vec<_>, D::Error>>()?;
+        let fresh_generation = fresh_generation
+            .to_raw()
+            .ok_or_else(|| D::Error::custom(FromEntriesError::GenerationOverflow))?;
+        Arena::from_raw_entries(entries, max_len, fresh_generation).map_err(D::Error::custom)
+    }
+}
+
+impl<M, I: ArenaIndex, G: ArenaGeneration> Serialize for ID<M, I, G> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.collect_str(self)
+        } else {
+            let bits = self.try_to_bits().ok_or_else(|| {
+                <S::Error as serde::ser::Error>::custom("ID index or generation does not fit in 32 bits")
+            })?;
+            serializer.serialize_u64(bits)
+        }
+    }
+}
+impl<'de, M, I: ArenaIndex, G: ArenaGeneration> Deserialize<'de> for ID<M, I, G> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let visitor = IdVisitor(PhantomData);
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(visitor)
+        } else {
+            deserializer.deserialize_u64(visitor)
+        }
+    }
+}
+
+/// Accepts both the string and the packed integer form of an `ID`.
+struct IdVisitor<M, I, G: ArenaGeneration>(PhantomData<ID<M, I, G>>);
+impl<M, I: ArenaIndex, G: ArenaGeneration> Visitor<'_> for IdVisitor<M, I, G> {
+    type Value = ID<M, I, G>;
+    fn expecting(&self, f: &mut Formatter) -> core::fmt::Result {
+        write!(f, "an arena ID")
+    }
+    fn visit_str<E: Error>(self, v: &str) -> Result<Self::Value, E> {
+        v.parse().map_err(E::custom)
+    }
+    fn visit_u64<E: Error>(self, v: u64) -> Result<Self::Value, E> {
+        ID::try_from_bits(v).ok_or_else(|| E::custom("ID index or generation out of range"))
+    }
+}
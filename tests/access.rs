@@ -0,0 +1,88 @@
+use typed_garena::{Arena, MoveError};
+
+#[test]
+fn get_disjoint_mut_rejects_aliasing_and_stale_ids() {
+    let mut arena = Arena::new();
+    let a = arena.insert(1);
+    let b = arena.insert(2);
+    let c = arena.insert(3);
+
+    assert!(arena.get_disjoint_mut([a, b, a]).is_none());
+    let stale = arena.insert(4);
+    arena.remove(stale);
+    assert!(arena.get_disjoint_mut([a, stale]).is_none());
+
+    let [x, y, z] = arena.get_disjoint_mut([c, a, b]).unwrap();
+    *x += 10;
+    *y += 20;
+    *z += 30;
+    assert_eq!(arena.values().copied().collect::<Vec<_>>(), [21, 32, 13]);
+}
+
+#[test]
+fn get2_mut_borrows_two_distinct_entries() {
+    let mut arena = Arena::new();
+    let a = arena.insert(String::from("a"));
+    let b = arena.insert(String::from("b"));
+
+    let (x, y) = arena.get2_mut(a, b).unwrap();
+    x.push_str(y);
+    y.clear();
+    assert_eq!(arena[a], "ab");
+    assert_eq!(arena[b], "");
+
+    assert!(arena.get2_mut(a, a).is_none());
+    arena.remove(b);
+    assert!(arena.get2_mut(a, b).is_none());
+}
+
+#[test]
+fn swap_exchanges_values_but_not_ids() {
+    let mut arena = Arena::new();
+    let a = arena.insert('a');
+    let b = arena.insert('b');
+    assert!(arena.swap(a, b));
+    assert_eq!((arena[a], arena[b]), ('b', 'a'));
+
+    assert!(!arena.swap(a, a));
+    arena.remove(b);
+    assert!(!arena.swap(a, b));
+    assert_eq!(arena[a], 'b');
+}
+
+#[test]
+fn first_and_last_skip_holes() {
+    let mut arena: Arena<i32> = (0..5).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    arena.remove(ids[0]);
+    arena.remove(ids[1]);
+    arena.remove(ids[4]);
+
+    assert_eq!(arena.first_id(), Some(ids[2]));
+    assert_eq!(arena.first(), Some(&2));
+    assert_eq!(arena.last_id(), Some(ids[3]));
+    assert_eq!(arena.last(), Some(&3));
+
+    arena.clear();
+    assert_eq!(arena.first_id(), None);
+    assert_eq!(arena.last(), None);
+}
+
+#[test]
+fn move_to_invalidates_the_old_id() {
+    let mut arena: Arena<i32> = (0..4).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    arena.remove(ids[0]);
+
+    let moved = arena.move_to(ids[3], 0).unwrap();
+    assert_eq!(moved.index(), 0);
+    assert_ne!(moved, ids[0]);
+    assert_eq!(arena[moved], 3);
+    assert!(!arena.contains(ids[3]));
+    assert!(!arena.contains(ids[0]));
+    arena.check_invariants().unwrap();
+
+    assert_eq!(arena.move_to(ids[3], 3), Err(MoveError::InvalidId));
+    assert_eq!(arena.move_to(ids[1], 2), Err(MoveError::NotFree));
+    assert_eq!(arena.move_to(ids[1], 10), Err(MoveError::OutOfBounds));
+}
@@ -0,0 +1,27 @@
+use typed_garena::{Arena, BuildError};
+
+#[test]
+fn builder_applies_its_settings() {
+    let mut arena = Arena::<i32>::builder()
+        .capacity(8)
+        .max_len(10)
+        .generation_start(5)
+        .build()
+        .unwrap();
+    assert!(arena.capacity() >= 8);
+    assert_eq!(arena.max_len(), Some(10));
+    assert_eq!(arena.insert(0).generation(), 5);
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn builder_rejects_capacity_above_max_len() {
+    let result = Arena::<i32>::builder().capacity(11).max_len(10).build();
+    assert_eq!(result.err(), Some(BuildError::CapacityAboveMaxLen));
+}
+
+#[test]
+fn builder_rejects_generation_overflow() {
+    let result = Arena::<i32, usize, u8>::builder().generation_start(u8::MAX).build();
+    assert_eq!(result.err(), Some(BuildError::GenerationOverflow));
+}
@@ -0,0 +1,104 @@
+use typed_garena::Arena;
+
+#[test]
+fn with_capacity_fits_its_capacity_without_reallocating() {
+    let mut arena = Arena::with_capacity(16);
+    let cap = arena.capacity();
+    assert!(cap >= 16);
+    for i in 0..cap {
+        arena.insert(i);
+    }
+    assert_eq!(arena.capacity(), cap);
+}
+
+#[test]
+fn reserve_fits_that_many_inserts() {
+    let mut arena = Arena::new();
+    arena.reserve(100);
+    let cap = arena.capacity();
+    for i in 0..100 {
+        arena.insert(i);
+    }
+    assert_eq!(arena.capacity(), cap);
+}
+
+#[test]
+fn reserve_counts_free_slots() {
+    let mut arena: Arena<i32> = (0..10).collect();
+    arena.shrink_to_fit();
+    let ids: Vec<_> = arena.keys().collect();
+    for id in ids {
+        arena.remove(id);
+    }
+    let cap = arena.capacity();
+
+    arena.reserve(10);
+    assert_eq!(arena.capacity(), cap);
+    for i in 0..10 {
+        arena.insert(i);
+    }
+    assert_eq!(arena.capacity(), cap);
+}
+
+#[test]
+fn shrink_to_fit_drops_the_free_tail() {
+    let mut arena: Arena<i32> = (0..100).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    for &id in &ids[10..] {
+        arena.remove(id);
+    }
+    arena.shrink_to_fit();
+    assert!(arena.capacity() < 100);
+    assert_eq!(arena.slots().count(), 10);
+
+    let stale = ids[50];
+    let new = arena.insert(0);
+    assert!(!arena.contains(stale));
+    assert!(arena.contains(new));
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn shrink_to_fit_keeps_slots_up_to_the_last_occupied_one() {
+    let mut arena: Arena<i32> = (0..100).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    for &id in &ids[..99] {
+        arena.remove(id);
+    }
+    arena.shrink_to_fit();
+    assert!(arena.capacity() >= 100);
+    assert_eq!(arena.slots().count(), 100);
+    assert_eq!(arena[ids[99]], 99);
+}
+
+#[test]
+fn clone_from_reuses_the_allocation() {
+    let mut target: Arena<i32> = Arena::with_capacity(64);
+    let cap = target.capacity();
+    for n in [10, 40, 0, 64] {
+        let source: Arena<i32> = (0..n).collect();
+        target.clone_from(&source);
+        assert_eq!(target, source);
+        assert_eq!(target.capacity(), cap);
+    }
+}
+
+#[test]
+fn shrink_to_respects_the_minimum_and_the_last_used_slot() {
+    let mut arena: Arena<i32> = Arena::with_capacity(1000);
+    arena.extend(0..50);
+    let ids: Vec<_> = arena.keys().collect();
+    for &id in &ids[20..] {
+        arena.remove(id);
+    }
+    arena.remove(ids[5]);
+
+    arena.shrink_to(100);
+    assert!(arena.capacity() >= 100);
+    assert!(arena.capacity() < 1000);
+
+    arena.shrink_to(0);
+    assert!(arena.capacity() >= 20);
+    assert_eq!(arena.slots().count(), 20);
+    assert_eq!(arena[ids[19]], 19);
+}
@@ -0,0 +1,22 @@
+use typed_garena::Arena;
+
+#[test]
+fn rollback_undoes_mixed_inserts_and_removes() {
+    let mut arena: Arena<i32> = (0..5).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    arena.remove(ids[1]);
+    let saved = arena.clone();
+    let checkpoint = arena.checkpoint();
+
+    arena.remove(ids[0]);
+    arena.remove(ids[3]);
+    let added = arena.insert(10);
+    arena.insert(11);
+    arena[ids[2]] = 20;
+
+    arena.rollback(checkpoint);
+    assert_eq!(arena, saved);
+    assert!(!arena.contains(added));
+    assert_eq!(arena.peek_next_id(), saved.peek_next_id());
+    arena.check_invariants().unwrap();
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "std")]
+
+use std::{collections::HashSet, thread};
+use typed_garena::ConcurrentArena;
+
+#[test]
+fn concurrent_inserts_all_resolve() {
+    const THREADS: usize = 8;
+    const PER_THREAD: usize = 2000;
+
+    let arena = ConcurrentArena::with_shards(4);
+    let ids: Vec<_> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..THREADS)
+            .map(|thread| {
+                let arena = &arena;
+                scope.spawn(move || {
+                    (0..PER_THREAD)
+                        .map(|i| {
+                            let t = thread * PER_THREAD + i;
+                            let id = arena.insert(t);
+                            if i % 3 == 0 {
+                                assert_eq!(arena.remove(id), Some(t));
+                            }
+                            (id, t)
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect()
+    });
+
+    let mut live = HashSet::new();
+    for &(id, t) in &ids {
+        if t % PER_THREAD % 3 == 0 {
+            assert!(!arena.contains(id));
+        } else {
+            assert_eq!(arena.get_cloned(id), Some(t));
+            assert!(live.insert(id));
+        }
+    }
+    assert_eq!(arena.len(), live.len());
+}
@@ -0,0 +1,98 @@
+use typed_garena::{Arena, ID};
+
+/// Slots 1, 3 and 4 are occupied, 0, 2 and 5 are free.
+fn arena_with_holes() -> (Arena<i32>, Vec<ID<i32>>) {
+    let mut arena: Arena<i32> = (0..6).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    for &i in &[0, 2, 5] {
+        arena.remove(ids[i]);
+    }
+    (arena, ids)
+}
+
+#[test]
+fn cursor_skips_holes_and_stops_at_both_ends() {
+    let (mut arena, ids) = arena_with_holes();
+    let mut cursor = arena.cursor_mut();
+    assert_eq!(cursor.id(), Some(ids[1]));
+    assert_eq!(cursor.peek_prev(), None);
+    assert!(!cursor.move_prev());
+    assert_eq!(cursor.id(), Some(ids[1]));
+
+    assert_eq!(cursor.peek_next(), Some((ids[3], &3)));
+    assert!(cursor.move_next());
+    assert_eq!(cursor.peek_prev(), Some((ids[1], &1)));
+    assert_eq!(cursor.peek_next(), Some((ids[4], &4)));
+
+    assert!(cursor.move_next());
+    assert_eq!(cursor.peek_next(), None);
+    assert!(!cursor.move_next());
+    assert_eq!(cursor.id(), Some(ids[4]));
+
+    assert!(cursor.move_prev());
+    assert!(cursor.move_prev());
+    assert_eq!(cursor.id(), Some(ids[1]));
+}
+
+#[test]
+fn cursor_get_rejects_the_current_entry_and_invalid_ids() {
+    let (mut arena, ids) = arena_with_holes();
+    let mut cursor = arena.cursor_mut();
+    cursor.move_next();
+
+    assert_eq!(cursor.get(ids[1]), Some(&1));
+    assert_eq!(cursor.get(ids[4]), Some(&4));
+    assert_eq!(cursor.get(ids[3]), None);
+    assert_eq!(cursor.get(ids[2]), None);
+    assert_eq!(cursor.get(ID::from_parts(100, 0)), None);
+    assert_eq!(cursor.get(ID::NULL), None);
+}
+
+#[test]
+fn neighbors_translate_indices_around_the_current_entry() {
+    let (mut arena, ids) = arena_with_holes();
+    let mut cursor = arena.cursor_mut();
+    cursor.move_next();
+
+    let (current, neighbors) = cursor.split().unwrap();
+    assert_eq!(*current, 3);
+    assert_eq!(neighbors.prev(), Some((ids[1], &1)));
+    assert_eq!(neighbors.next(), Some((ids[4], &4)));
+    assert_eq!(neighbors.get(ids[1]), Some(&1));
+    assert_eq!(neighbors.get(ids[4]), Some(&4));
+    assert_eq!(neighbors.get(ids[3]), None);
+    assert_eq!(neighbors.get(ids[0]), None);
+    assert_eq!(neighbors.get(ids[5]), None);
+    assert_eq!(neighbors.get(ID::from_parts(100, 0)), None);
+    assert_eq!(neighbors.get(ID::NULL), None);
+    *current += neighbors.prev().unwrap().1;
+    assert_eq!(arena[ids[3]], 4);
+}
+
+#[test]
+fn neighbors_at_the_first_and_last_slot() {
+    let mut arena: Arena<i32> = (0..2).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    let mut cursor = arena.cursor_mut();
+
+    let (_, neighbors) = cursor.split().unwrap();
+    assert_eq!(neighbors.prev(), None);
+    assert_eq!(neighbors.next(), Some((ids[1], &1)));
+
+    cursor.move_next();
+    let (_, neighbors) = cursor.split().unwrap();
+    assert_eq!(neighbors.prev(), Some((ids[0], &0)));
+    assert_eq!(neighbors.next(), None);
+    assert_eq!(neighbors.get(ids[0]), Some(&0));
+}
+
+#[test]
+fn cursor_over_an_empty_arena() {
+    let mut arena: Arena<i32> = Arena::new();
+    let mut cursor = arena.cursor_mut();
+    assert_eq!(cursor.id(), None);
+    assert!(!cursor.move_next());
+    assert!(!cursor.move_prev());
+    assert_eq!(cursor.peek_next(), None);
+    assert!(cursor.split().is_none());
+}
@@ -0,0 +1,66 @@
+use typed_garena::Arena;
+
+/// Two arenas holding 0, 1, 2 at the same IDs, whose free lists of
+/// slots 3 and 4 are in opposite orders.
+fn same_entries_different_free_lists() -> (Arena<i32>, Arena<i32>) {
+    let mut a: Arena<i32> = (0..5).collect();
+    let mut b = a.clone();
+    let ids: Vec<_> = a.keys().collect();
+    a.remove(ids[3]);
+    a.remove(ids[4]);
+    b.remove(ids[4]);
+    b.remove(ids[3]);
+    assert_ne!(a.peek_next_id(), b.peek_next_id());
+    (a, b)
+}
+
+#[test]
+fn equality_ignores_free_list_order() {
+    let (a, b) = same_entries_different_free_lists();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn equality_sees_reused_slots() {
+    let mut a = Arena::new();
+    let id = a.insert(1);
+    let mut b = a.clone();
+    b.remove(id);
+    b.insert(1);
+    assert_eq!(a.values().collect::<Vec<_>>(), b.values().collect::<Vec<_>>());
+    assert_ne!(a, b);
+}
+
+#[test]
+fn equal_arenas_hash_the_same() {
+    use std::hash::{BuildHasher, RandomState};
+
+    let (a, b) = same_entries_different_free_lists();
+    let state = RandomState::new();
+    assert_eq!(state.hash_one(&a), state.hash_one(&b));
+
+    let mut c = a.clone();
+    let id = c.keys().next().unwrap();
+    c[id] = 100;
+    assert_ne!(a, c);
+    assert_ne!(state.hash_one(&a), state.hash_one(&c));
+}
+
+#[test]
+fn diff_reports_added_removed_and_changed_entries() {
+    let old: Arena<i32> = (0..4).collect();
+    let ids: Vec<_> = old.keys().collect();
+    let mut new = old.clone();
+    new.remove(ids[1]);
+    new[ids[2]] = 20;
+    let reused = new.insert(10);
+    let appended = new.insert(11);
+    assert_eq!(reused.index(), ids[1].index());
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.added, [reused, appended]);
+    assert_eq!(diff.removed, [ids[1]]);
+    assert_eq!(diff.changed, [(ids[2], &2, &20)]);
+    assert!(!diff.is_empty());
+    assert!(old.diff(&old.clone()).is_empty());
+}
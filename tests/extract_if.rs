@@ -0,0 +1,29 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use typed_garena::Arena;
+
+#[test]
+fn panicking_predicate_does_not_abort() {
+    let mut arena: Arena<u32> = (0..5).collect();
+    let mut calls = 0;
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        let mut extracted = arena.extract_if(|_, _| {
+            calls += 1;
+            assert!(calls < 2, "predicate failed");
+            true
+        });
+        extracted.next();
+        extracted.next();
+    }));
+
+    assert!(result.is_err());
+    assert_eq!(arena.len(), 4);
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn dropping_early_still_extracts_the_rest() {
+    let mut arena: Arena<u32> = (0..6).collect();
+    arena.extract_if(|_, v| *v % 2 == 0).next();
+    assert_eq!(arena.values().copied().collect::<Vec<_>>(), [1, 3, 5]);
+    arena.check_invariants().unwrap();
+}
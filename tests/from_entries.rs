@@ -0,0 +1,75 @@
+use typed_garena::{Arena, FromEntriesError, RawEntry};
+
+fn free(next_free: Option<usize>) -> RawEntry<i32> {
+    RawEntry::Free {
+        next_generation: 0,
+        next_free,
+    }
+}
+fn occupied(value: i32) -> RawEntry<i32> {
+    RawEntry::Occupied { generation: 0, value }
+}
+
+#[test]
+fn from_entries_with_keeps_trimmed_ids_stale() {
+    let mut arena = Arena::new();
+    arena.insert(0);
+    let stale = arena.insert(1);
+    arena.remove(stale);
+    arena.shrink_to_fit();
+    assert_eq!(arena.fresh_generation(), 1);
+
+    let mut rebuilt = Arena::from_entries_with([occupied(0)], arena.fresh_generation(), Some(4)).unwrap();
+    assert_eq!(rebuilt.max_len(), Some(4));
+    let new = rebuilt.insert(2);
+    assert_eq!(new, arena.insert(2));
+    assert_ne!(new, stale);
+    assert!(!rebuilt.contains(stale));
+}
+
+#[test]
+fn from_entries_builds_the_free_list() {
+    let mut arena = Arena::<i32>::from_entries([free(Some(2)), occupied(1), free(None)]).unwrap();
+    arena.check_invariants().unwrap();
+    assert_eq!(arena.insert(3).index(), 0);
+    assert_eq!(arena.insert(4).index(), 2);
+}
+
+#[test]
+fn from_entries_rejects_too_many_entries() {
+    let entries = (0..256).map(|value| RawEntry::Occupied { generation: 0, value });
+    let result = Arena::<i32, u8>::from_entries(entries);
+    assert_eq!(result.err(), Some(FromEntriesError::TooManyEntries));
+}
+
+#[test]
+fn from_entries_rejects_generation_overflow() {
+    let entries = [RawEntry::Occupied {
+        generation: u32::MAX,
+        value: 0,
+    }];
+    assert_eq!(Arena::<i32>::from_entries(entries).err(), Some(FromEntriesError::GenerationOverflow));
+    let result = Arena::<i32>::from_entries_with([occupied(0)], u32::MAX, None);
+    assert_eq!(result.err(), Some(FromEntriesError::GenerationOverflow));
+}
+
+#[test]
+fn from_entries_rejects_broken_free_lists() {
+    let cases = [
+        (vec![free(Some(5))], FromEntriesError::LinkOutOfBounds),
+        (vec![free(Some(1)), occupied(0)], FromEntriesError::LinkToNonFree),
+        (vec![free(Some(2)), free(Some(2)), free(None)], FromEntriesError::LinkedTwice),
+        (vec![free(None), free(None)], FromEntriesError::NotSingleChain),
+        (vec![free(Some(1)), free(Some(0))], FromEntriesError::Cycle),
+    ];
+    for (entries, error) in cases {
+        assert_eq!(Arena::<i32>::from_entries(entries).err(), Some(error));
+    }
+}
+
+#[test]
+fn from_entries_with_rejects_entries_above_max_len() {
+    let entries = [occupied(0), RawEntry::Reserved { generation: 0 }];
+    let result = Arena::<i32>::from_entries_with(entries, 0, Some(1));
+    assert_eq!(result.err(), Some(FromEntriesError::AboveMaxLen));
+}
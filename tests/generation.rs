@@ -0,0 +1,40 @@
+use typed_garena::{Arena, Generation, RawEntry, Slot};
+
+#[test]
+fn slot_retires_at_the_last_generation() {
+    let last = Generation::MAX - 1;
+    let mut arena: Arena<i32> = Arena::from_entries([RawEntry::Occupied {
+        generation: last,
+        value: 1,
+    }])
+    .unwrap();
+    let id = arena.keys().next().unwrap();
+    assert_eq!(id.generation(), last);
+
+    assert_eq!(arena.remove(id), Some(1));
+    assert!(matches!(arena.slots().next(), Some(Slot::Retired { index: 0 })));
+    assert!(!arena.contains(id));
+
+    let next = arena.insert(2);
+    assert_eq!(next.index(), 1);
+    assert!(!arena.contains(id));
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn small_generations_retire_after_their_reuses() {
+    let mut arena: Arena<i32, usize, u8> = Arena::default();
+    let first = arena.insert(0);
+    let mut id = first;
+    for _ in 0..u8::MAX - 1 {
+        arena.remove(id);
+        id = arena.insert(0);
+        assert_eq!(id.index(), 0);
+    }
+    assert_eq!(id.generation(), u8::MAX - 1);
+
+    arena.remove(id);
+    assert_eq!(arena.insert(0).index(), 1);
+    assert!(!arena.contains(first));
+    assert!(!arena.contains(id));
+}
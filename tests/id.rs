@@ -0,0 +1,58 @@
+use core::mem::size_of;
+use typed_garena::{Arena, InsertAtError, ParseIDError, ID};
+
+#[test]
+fn option_id_uses_the_generation_niche() {
+    assert_eq!(size_of::<Option<ID<()>>>(), size_of::<ID<()>>());
+}
+
+#[test]
+fn null_round_trips_through_bits() {
+    let null = ID::<()>::NULL;
+    assert_eq!(null.to_bits(), u32::MAX as u64);
+    assert!(ID::<()>::from_bits(null.to_bits()).is_null());
+
+    let id = ID::<()>::from_parts(3, 7);
+    assert_eq!(ID::<()>::from_bits(id.to_bits()), id);
+}
+
+#[test]
+fn arenas_reject_null() {
+    let mut arena = Arena::new();
+    arena.insert(1);
+    assert_eq!(arena.get(ID::NULL), None);
+    assert!(!arena.contains(ID::NULL));
+    assert_eq!(arena.insert_at(ID::NULL, 2), Err(InsertAtError::Null));
+    assert_eq!(arena.len(), 1);
+}
+
+#[test]
+fn parsing_rejects_malformed_ids() {
+    assert_eq!("(1-2".parse::<ID>(), Err(ParseIDError::MissingParenthesis));
+    assert_eq!("1-2".parse::<ID>(), Err(ParseIDError::MissingParenthesis));
+    assert_eq!("(12)".parse::<ID>(), Err(ParseIDError::MissingSeparator));
+    assert!(matches!("(a-1)".parse::<ID>(), Err(ParseIDError::InvalidIndex(_))));
+    assert!(matches!("(1-b)".parse::<ID>(), Err(ParseIDError::InvalidGeneration(_))));
+}
+
+#[test]
+fn display_round_trips_through_parse() {
+    for id in [ID::<()>::from_parts(0, 0), ID::from_parts(5, 0), ID::from_parts(5, 3), ID::NULL] {
+        assert_eq!(id.to_string().parse(), Ok(id));
+    }
+    assert_eq!(ID::<()>::from_parts(5, 3).to_string(), "(5-3)");
+}
+
+#[test]
+fn any_generation_at_the_null_index_is_null() {
+    let null = ID::<()>::from_parts(usize::MAX, 3);
+    assert!(null.is_null());
+    assert_ne!(null, ID::NULL);
+    assert_eq!(null.to_bits(), ID::<()>::NULL.to_bits());
+    assert_eq!(ID::<()>::from_bits(null.to_bits()), ID::NULL);
+
+    let mut arena = Arena::new();
+    arena.insert(1);
+    assert!(!arena.contains(null.rebrand()));
+    assert_eq!(arena.insert_at(null.rebrand(), 2), Err(InsertAtError::Null));
+}
@@ -0,0 +1,42 @@
+#![cfg(feature = "std")]
+
+use typed_garena::IndexedArena;
+
+fn names() -> IndexedArena<String, String> {
+    IndexedArena::new(|name: &String| name.to_lowercase())
+}
+
+#[test]
+fn insert_rejects_duplicate_keys() {
+    let mut arena = names();
+    let id = arena.insert("Alice".to_owned()).unwrap();
+    assert_eq!(arena.insert("ALICE".to_owned()), Err("ALICE".to_owned()));
+    assert_eq!(arena.len(), 1);
+    assert_eq!(arena.get(id).map(String::as_str), Some("Alice"));
+    assert_eq!(arena.get_by_key("alice"), Some(id));
+}
+
+#[test]
+fn intern_returns_the_existing_id() {
+    let mut arena = names();
+    let alice = arena.intern("Alice".to_owned());
+    let bob = arena.intern("Bob".to_owned());
+    assert_ne!(alice, bob);
+    assert_eq!(arena.intern("alice".to_owned()), alice);
+    assert_eq!(arena.len(), 2);
+    assert_eq!(arena.get(alice).map(String::as_str), Some("Alice"));
+}
+
+#[test]
+fn remove_frees_the_key() {
+    let mut arena = names();
+    let id = arena.insert("Alice".to_owned()).unwrap();
+    assert_eq!(arena.remove(id).as_deref(), Some("Alice"));
+    assert!(!arena.contains_key("alice"));
+    assert_eq!(arena.remove(id), None);
+
+    let again = arena.insert("alice".to_owned()).unwrap();
+    assert_ne!(again, id);
+    assert_eq!(arena.get_by_key("alice"), Some(again));
+    assert!(!arena.contains(id));
+}
@@ -0,0 +1,146 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use typed_garena::{Arena, ID};
+
+#[test]
+fn panicking_insert_with_id_leaves_the_arena_intact() {
+    let mut arena = Arena::new();
+    let a = arena.insert(1);
+    arena.insert(2);
+    arena.remove(a);
+
+    // Once with a free slot to reuse and once with a slot to push.
+    for len in 1..3 {
+        let result = catch_unwind(AssertUnwindSafe(|| arena.insert_with_id(|_| panic!("no value"))));
+        assert!(result.is_err());
+        arena.check_invariants().unwrap();
+        assert_eq!(arena.len(), len);
+
+        let id = arena.peek_next_id();
+        assert_eq!(arena.insert_with_id(|id| id.index()), id);
+        arena.check_invariants().unwrap();
+    }
+}
+
+#[test]
+fn reserve_ids_allows_values_to_refer_to_each_other() {
+    struct Node {
+        next: ID<Node>,
+    }
+
+    let mut arena = Arena::new();
+    let ids = arena.reserve_ids(3);
+    assert_eq!(arena.reserved_len(), 3);
+    assert!(arena.get(ids[0]).is_none());
+    assert_eq!(arena.iter().count(), 0);
+
+    for (i, &id) in ids.iter().enumerate() {
+        arena.fill(id, Node { next: ids[(i + 1) % ids.len()] });
+    }
+    assert_eq!(arena.reserved_len(), 0);
+    assert_eq!(arena[arena[arena[ids[0]].next].next].next, ids[0]);
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn reserve_ids_reserves_nothing_if_too_few_slots_are_left() {
+    let mut arena = Arena::<()>::builder().max_len(2).build().unwrap();
+    arena.insert(());
+    let result = catch_unwind(AssertUnwindSafe(|| arena.reserve_ids(2)));
+    assert!(result.is_err());
+    assert_eq!(arena.reserved_len(), 0);
+    assert_eq!(arena.reserve_ids(1).len(), 1);
+}
+
+#[test]
+fn clear_invalidates_ids_of_reused_slots() {
+    let mut arena = Arena::new();
+    let old = arena.insert("old");
+    arena.clear();
+
+    let new = arena.insert("new");
+    assert_eq!(new.index(), old.index());
+    assert_ne!(new, old);
+    assert_eq!(arena.get(old), None);
+    assert_eq!(arena.get(new), Some(&"new"));
+}
+
+#[test]
+fn extend_fills_free_slots_first() {
+    let mut arena: Arena<i32> = (0..5).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    arena.remove(ids[1]);
+    arena.remove(ids[3]);
+
+    arena.extend([10, 11, 12]);
+    let mut indices: Vec<_> = arena.iter().filter(|&(_, &t)| t >= 10).map(|(id, _)| id.index()).collect();
+    indices.sort();
+    assert_eq!(indices, [1, 3, 5]);
+    assert_eq!(arena.slots().count(), 6);
+}
+
+#[test]
+fn get_or_insert_with_returns_live_entries() {
+    let mut arena = Arena::new();
+    let id = arena.insert(1);
+    *arena.get_or_insert_with(id, || unreachable!()) += 1;
+    assert_eq!(arena[id], 2);
+    assert_eq!(arena.len(), 1);
+}
+
+#[test]
+fn get_or_insert_with_brings_the_next_id_to_life() {
+    let mut arena = Arena::new();
+    let old = arena.insert(1);
+    arena.remove(old);
+
+    let next = arena.peek_next_id();
+    assert_eq!(*arena.get_or_insert_with(next, || 5), 5);
+    assert_eq!(arena[next], 5);
+    arena.check_invariants().unwrap();
+
+    let result = catch_unwind(AssertUnwindSafe(|| *arena.get_or_insert_with(old, || 6)));
+    assert!(result.is_err());
+}
+
+#[test]
+fn peek_next_id_matches_the_next_insert() {
+    let mut arena = Arena::new();
+    for i in 0..4 {
+        let next = arena.peek_next_id();
+        assert_eq!(arena.insert(i), next);
+    }
+    let ids: Vec<_> = arena.keys().collect();
+    arena.remove(ids[2]);
+    arena.remove(ids[0]);
+    for i in 0..3 {
+        let next = arena.peek_next_id();
+        assert_eq!(arena.insert(i), next);
+    }
+}
+
+#[test]
+fn resurrect_brings_back_a_removed_id() {
+    let mut arena = Arena::new();
+    let id = arena.insert(1);
+    arena.remove(id);
+
+    assert_eq!(arena.resurrect(id, 2), Ok(()));
+    assert_eq!(arena[id], 2);
+    assert_eq!(arena.resurrect(id, 3), Err(3));
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn resurrect_fails_once_the_slot_was_reused() {
+    let mut arena = Arena::new();
+    let id = arena.insert(1);
+    arena.remove(id);
+    let thief = arena.insert(2);
+    assert_eq!(thief.index(), id.index());
+
+    assert_eq!(arena.resurrect(id, 3), Err(3));
+    arena.remove(thief);
+    assert_eq!(arena.resurrect(id, 4), Err(4));
+    assert_eq!(arena.resurrect(thief, 5), Ok(()));
+    assert!(!arena.contains(id));
+}
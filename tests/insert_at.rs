@@ -0,0 +1,67 @@
+use typed_garena::{Arena, InsertAtError, Slot, ID};
+
+#[test]
+fn insert_at_rejects_slots_in_use() {
+    let mut arena: Arena<i32, usize, u8> = Arena::default();
+    let mut retiring = arena.insert(0);
+    while retiring.generation() < u8::MAX - 1 {
+        arena.remove(retiring);
+        retiring = arena.insert(0);
+    }
+    arena.remove(retiring);
+    let occupied = arena.insert(1);
+    let reserved = arena.reserve_id();
+
+    assert_eq!(arena.insert_at(retiring, 2), Err(InsertAtError::Retired));
+    assert_eq!(arena.insert_at(occupied, 2), Err(InsertAtError::Occupied));
+    assert_eq!(arena.insert_at(reserved, 2), Err(InsertAtError::Reserved));
+    assert_eq!(arena.insert_at(ID::NULL, 2), Err(InsertAtError::Null));
+    assert_eq!(arena[occupied], 1);
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn insert_at_respects_max_len() {
+    let mut arena = Arena::with_max_len(1);
+    arena.insert(0);
+    assert_eq!(arena.insert_at(ID::from_parts(3, 0), 1), Err(InsertAtError::Full));
+    assert_eq!(arena.slots().count(), 1);
+}
+
+#[test]
+fn insert_at_past_the_end_creates_free_slots() {
+    let mut arena = Arena::new();
+    arena.insert(0);
+    let id = ID::from_parts(4, 2);
+    arena.insert_at(id, 4).unwrap();
+    arena.check_invariants().unwrap();
+
+    assert_eq!(arena[id], 4);
+    assert_eq!(arena.len(), 2);
+    let free: Vec<_> = arena.slots().filter(|slot| matches!(slot, Slot::Free { .. })).map(|slot| slot.index()).collect();
+    assert_eq!(free, [1, 2, 3]);
+
+    let mut filled: Vec<_> = (0..3).map(|t| arena.insert(t).index()).collect();
+    filled.sort();
+    assert_eq!(filled, [1, 2, 3]);
+    assert_eq!(arena.insert(9).index(), 5);
+}
+
+#[test]
+fn insert_at_splices_a_free_slot_out_of_the_middle_of_the_free_list() {
+    let mut arena: Arena<i32> = (0..5).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    for &i in &[0, 2, 4] {
+        arena.remove(ids[i]);
+    }
+
+    let middle = ID::from_parts(2, 1);
+    arena.insert_at(middle, 20).unwrap();
+    arena.check_invariants().unwrap();
+    assert_eq!(arena[middle], 20);
+    assert!(!arena.contains(ids[2]));
+
+    assert_eq!(arena.insert(40).index(), 4);
+    assert_eq!(arena.insert(0).index(), 0);
+    assert_eq!(arena.insert(5).index(), 5);
+}
@@ -0,0 +1,109 @@
+use typed_garena::Arena;
+
+#[test]
+fn iter_mut_yields_current_generations() {
+    let mut arena = Arena::new();
+    let a = arena.insert(1);
+    let b = arena.insert(2);
+    arena.remove(a);
+    let c = arena.insert(3);
+    assert_eq!(c.index(), a.index());
+
+    let ids: Vec<_> = arena.iter_mut().map(|(id, _)| id).collect();
+    assert_eq!(ids, [c, b]);
+    for id in ids {
+        assert!(arena.contains(id));
+        assert!(arena.get(id).is_some());
+    }
+    assert!(!arena.contains(a));
+}
+
+#[test]
+fn values_visit_the_occupied_entries_in_order() {
+    let mut arena: Arena<i32> = (0..6).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    arena.remove(ids[0]);
+    arena.remove(ids[3]);
+    arena.reserve_id();
+
+    assert_eq!(arena.values().copied().collect::<Vec<_>>(), [1, 2, 4, 5]);
+    for t in arena.values_mut() {
+        *t *= 10;
+    }
+    assert_eq!(arena.values().copied().collect::<Vec<_>>(), [10, 20, 40, 50]);
+}
+
+#[test]
+fn iter_len_tracks_the_remaining_entries() {
+    let mut arena: Arena<i32> = (0..5).collect();
+    let second = arena.keys().nth(1).unwrap();
+    arena.remove(second);
+
+    let mut iter = arena.iter();
+    assert_eq!(iter.len(), arena.len());
+    for remaining in (0..arena.len()).rev() {
+        iter.next().unwrap();
+        assert_eq!(iter.len(), remaining);
+    }
+    assert!(iter.next().is_none());
+
+    let mut iter = arena.iter();
+    iter.next_back();
+    assert_eq!(iter.len(), arena.len() - 1);
+}
+
+#[test]
+fn indices_run_the_same_in_both_directions() {
+    let mut arena: Arena<i32> = (0..8).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    arena.remove(ids[0]);
+    arena.remove(ids[4]);
+    arena.remove(ids[7]);
+
+    let forward: Vec<_> = arena.indices().collect();
+    let mut backward: Vec<_> = arena.indices().rev().collect();
+    backward.reverse();
+    assert_eq!(forward, backward);
+
+    let mut indices = arena.indices();
+    assert_eq!(indices.next(), Some(ids[1]));
+    assert_eq!(indices.next_back(), Some(ids[6]));
+    assert_eq!(indices.collect::<Vec<_>>(), [ids[2], ids[3], ids[5]]);
+}
+
+#[test]
+fn cloned_iterators_advance_independently() {
+    let arena: Arena<i32> = (0..4).collect();
+    let mut iter = arena.iter();
+    iter.next();
+
+    let mut clone = iter.clone();
+    assert_eq!(clone.next().map(|(_, &t)| t), Some(1));
+    assert_eq!(clone.next().map(|(_, &t)| t), Some(2));
+    assert_eq!(iter.len(), 3);
+    assert_eq!(iter.next().map(|(_, &t)| t), Some(1));
+    assert_eq!(clone.len(), 1);
+
+    let mut values = arena.values();
+    values.next_back();
+    assert_eq!(values.clone().collect::<Vec<_>>(), [&0, &1, &2]);
+    assert_eq!(values.len(), 3);
+}
+
+#[test]
+fn occupied_mask_marks_exactly_the_occupied_slots() {
+    let mut arena: Arena<usize> = (0..130).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    let holes = [0, 5, 63, 64, 100, 129];
+    for &i in &holes {
+        arena.remove(ids[i]);
+    }
+    arena.reserve_id();
+
+    let mask = arena.occupied_mask();
+    assert_eq!(mask.len(), 3);
+    for i in 0..192 {
+        let bit = mask[i / 64] >> (i % 64) & 1 == 1;
+        assert_eq!(bit, i < 130 && !holes.contains(&i), "slot {i}");
+    }
+}
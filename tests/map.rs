@@ -0,0 +1,34 @@
+use typed_garena::Arena;
+
+#[test]
+fn map_keeps_every_id() {
+    let mut orig: Arena<i32> = (0..6).collect();
+    let ids: Vec<_> = orig.keys().collect();
+    orig.remove(ids[2]);
+    orig.insert(20);
+    let kept: Vec<_> = orig.iter().map(|(id, &t)| (id, t)).collect();
+
+    let f = |t: i32| format!("{t}!");
+    let mapped = orig.map(|_, t| f(t));
+    assert_eq!(mapped.len(), kept.len());
+    for (id, t) in kept {
+        assert_eq!(mapped[id.rebrand()], f(t));
+    }
+    assert!(!mapped.contains(ids[2].rebrand()));
+}
+
+#[test]
+fn filter_map_keeps_the_ids_of_survivors() {
+    let orig: Arena<i32> = (0..6).collect();
+    let ids: Vec<_> = orig.keys().collect();
+
+    let mut mapped = orig.filter_map(|_, t| (t % 2 == 1).then_some(t * 10));
+    assert_eq!(mapped.len(), 3);
+    for (i, id) in ids.iter().enumerate() {
+        assert_eq!(mapped.get(id.rebrand()).copied(), (i % 2 == 1).then_some(i as i32 * 10));
+    }
+
+    let new = mapped.insert(0);
+    assert!(ids.iter().all(|id| id.rebrand() != new));
+    mapped.check_invariants().unwrap();
+}
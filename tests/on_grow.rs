@@ -0,0 +1,46 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use typed_garena::{Arena, ID};
+
+#[test]
+fn bulk_inserts_report_growth() {
+    static GROWTHS: AtomicUsize = AtomicUsize::new(0);
+    fn on_grow(old: usize, new: usize) {
+        assert!(new > old);
+        GROWTHS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let mut arena = Arena::new();
+    arena.set_on_grow(Some(on_grow));
+    arena.extend(0..100);
+    assert_eq!(GROWTHS.load(Ordering::Relaxed), 1);
+
+    arena.insert_batch((0..1000).collect());
+    assert_eq!(GROWTHS.load(Ordering::Relaxed), 2);
+
+    arena.reserve(100_000);
+    assert_eq!(GROWTHS.load(Ordering::Relaxed), 2);
+
+    #[cfg(feature = "std")]
+    {
+        arena.append((0..1_000_000).collect::<Arena<i32>>());
+        assert_eq!(GROWTHS.load(Ordering::Relaxed), 3);
+    }
+}
+
+#[test]
+fn insert_at_reports_growth() {
+    static GROWTHS: AtomicUsize = AtomicUsize::new(0);
+    fn on_grow(old: usize, new: usize) {
+        assert!(new > old);
+        GROWTHS.fetch_add(1, Ordering::Relaxed);
+    }
+
+    let mut arena = Arena::new();
+    arena.set_on_grow(Some(on_grow));
+    arena.insert_at(ID::from_parts(100, 0), 1).unwrap();
+    assert_eq!(GROWTHS.load(Ordering::Relaxed), 1);
+    assert!(arena.capacity() >= 101);
+
+    arena.insert_at(ID::from_parts(50, 0), 2).unwrap();
+    assert_eq!(GROWTHS.load(Ordering::Relaxed), 1);
+}
@@ -0,0 +1,22 @@
+#![cfg(feature = "rayon")]
+
+use rayon::iter::ParallelIterator;
+use typed_garena::Arena;
+
+#[test]
+fn par_iter_matches_iter() {
+    let mut arena: Arena<u64> = (0..10_000).collect();
+    let ids: Vec<_> = arena.keys().step_by(3).collect();
+    for id in ids {
+        arena.remove(id);
+    }
+
+    let serial: u64 = arena.iter().map(|(id, &t)| t * id.index() as u64).sum();
+    let parallel: u64 = arena.par_iter().map(|(id, &t)| t * id.index() as u64).sum();
+    assert_eq!(parallel, serial);
+    assert_eq!(arena.par_iter().count(), arena.len());
+
+    let before: u64 = arena.values().sum();
+    arena.par_iter_mut().for_each(|(_, t)| *t += 1);
+    assert_eq!(arena.values().sum::<u64>(), before + arena.len() as u64);
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "std")]
+
+use std::collections::HashSet;
+use typed_garena::{Arena, Slot};
+
+#[test]
+fn compact_remaps_every_entry() {
+    let mut arena: Arena<i32> = (0..8).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    for &i in &[1, 2, 5] {
+        arena.remove(ids[i]);
+    }
+    let before: Vec<_> = arena.iter().map(|(id, &t)| (id, t)).collect();
+
+    let remap = arena.compact();
+    assert_eq!(remap.len(), before.len());
+    for (old, t) in before {
+        assert_eq!(arena[remap[&old]], t);
+    }
+    assert_eq!(arena.slots().count(), arena.len());
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn compact_leaves_reserved_and_retired_slots_in_place() {
+    let mut arena: Arena<i32, usize, u8> = Arena::default();
+    let mut retiring = arena.insert(0);
+    while retiring.generation() < u8::MAX - 1 {
+        arena.remove(retiring);
+        retiring = arena.insert(0);
+    }
+    arena.remove(retiring);
+    let free = arena.insert(1);
+    let reserved = arena.reserve_id();
+    let last = arena.insert(2);
+    arena.remove(free);
+
+    let remap = arena.compact();
+    assert_eq!(remap[&last].index(), 1);
+    assert_eq!(arena[remap[&last]], 2);
+    assert!(arena.slots().any(|slot| matches!(slot, Slot::Retired { index: 0 })));
+    assert!(arena.slots().any(|slot| matches!(slot, Slot::Reserved { id } if id == reserved)));
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn append_remaps_the_moved_entries() {
+    let mut arena: Arena<i32> = (0..3).collect();
+    let first = arena.keys().next().unwrap();
+    arena.remove(first);
+
+    let mut other: Arena<i32> = (10..14).collect();
+    let gone = other.keys().nth(1).unwrap();
+    other.remove(gone);
+    let moved: Vec<_> = other.iter().map(|(id, &t)| (id, t)).collect();
+
+    let remap = arena.append(other);
+    assert_eq!(remap.len(), moved.len());
+    assert!(!remap.contains_key(&gone));
+    for (old, t) in moved {
+        assert_eq!(arena[remap[&old]], t);
+    }
+    assert_eq!(arena.len(), 5);
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn reconcile_keeps_every_entry_of_a_kept_key() {
+    let mut arena = Arena::new();
+    let a1 = arena.insert(("a", 1));
+    let b = arena.insert(("b", 2));
+    let a2 = arena.insert(("a", 3));
+    let c = arena.insert(("c", 4));
+    let b2 = arena.insert(("b", 5));
+
+    let keep = HashSet::from(["a", "c", "d"]);
+    let removed = arena.reconcile(&keep, |&(key, _)| key);
+    assert_eq!(removed, [b, b2]);
+    assert_eq!(arena.keys().collect::<Vec<_>>(), [a1, a2, c]);
+}
+
+#[test]
+fn compact_with_reports_only_moved_entries() {
+    let mut arena: Arena<i32> = (0..8).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    for &i in &[1, 4, 6] {
+        arena.remove(ids[i]);
+    }
+    let mut compacted = arena.clone();
+    let remap = compacted.compact();
+
+    let mut moves = Vec::new();
+    arena.compact_with(|old, new| moves.push((old, new)));
+    assert_eq!(moves.len(), 2);
+    for &(old, new) in &moves {
+        assert_ne!(old, new);
+        assert_eq!(remap[&old], new);
+    }
+    for &i in &[0, 2, 3] {
+        assert!(moves.iter().all(|&(old, _)| old != ids[i]));
+        assert_eq!(arena[ids[i]], i as i32);
+    }
+
+    assert_eq!(arena, compacted);
+    assert_eq!(arena.slots().count(), compacted.slots().count());
+    assert_eq!(arena.peek_next_id(), compacted.peek_next_id());
+}
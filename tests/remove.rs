@@ -0,0 +1,157 @@
+use typed_garena::{Arena, Slot, ID};
+
+#[test]
+fn remove_index_range_reuses_the_range_in_order() {
+    let mut arena: Arena<i32> = (0..10).collect();
+    let far = arena.keys().nth(8).unwrap();
+    arena.remove(far);
+
+    let removed = arena.remove_index_range(2..5);
+    assert_eq!(removed.iter().map(|&(_, t)| t).collect::<Vec<_>>(), [2, 3, 4]);
+    arena.check_invariants().unwrap();
+
+    let indices: Vec<_> = (0..4).map(|t| arena.insert(t).index()).collect();
+    assert_eq!(indices, [2, 3, 4, 8]);
+}
+
+#[test]
+fn clear_and_shrink_keeps_slots_up_to_the_last_retired_one() {
+    let mut arena: Arena<i32, usize, u8> = Arena::default();
+    arena.insert(0);
+    let mut id = arena.insert(1);
+    while id.generation() < u8::MAX - 1 {
+        arena.remove(id);
+        id = arena.insert(1);
+    }
+    arena.remove(id);
+    arena.insert(2);
+    assert_eq!(arena.slots().count(), 3);
+
+    arena.clear_and_shrink();
+    arena.check_invariants().unwrap();
+    let slots: Vec<_> = arena.slots().map(|slot| matches!(slot, Slot::Retired { .. })).collect();
+    assert_eq!(slots, [false, true]);
+}
+
+#[test]
+fn retain_cross_sees_entries_it_already_rejected() {
+    // Each node points at the one before it and is kept if that one is odd,
+    // which has often been rejected by the time the later node is visited.
+    struct Node {
+        value: i32,
+        prev: Option<ID<Node>>,
+    }
+
+    let mut arena = Arena::new();
+    let ids = arena.reserve_ids(5);
+    for (i, &id) in ids.iter().enumerate() {
+        arena.fill(id, Node { value: i as i32, prev: i.checked_sub(1).map(|prev| ids[prev]) });
+    }
+
+    arena.retain_cross(|_, node, arena| node.prev.is_some_and(|prev| arena[prev].value % 2 == 1));
+    let kept: Vec<_> = arena.values().map(|node| node.value).collect();
+    assert_eq!(kept, [2, 4]);
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn remove_index_range_retires_exhausted_slots() {
+    let mut arena: Arena<i32, usize, u8> = Arena::default();
+    let mut id = arena.insert(0);
+    while id.generation() < u8::MAX - 1 {
+        arena.remove(id);
+        id = arena.insert(0);
+    }
+    arena.insert(1);
+    arena.insert(2);
+
+    let removed = arena.remove_index_range(..);
+    assert_eq!(removed.iter().map(|&(_, t)| t).collect::<Vec<_>>(), [0, 1, 2]);
+    assert!(arena.is_empty());
+    assert_eq!(arena.retired_count(), 1);
+    arena.check_invariants().unwrap();
+    assert_eq!(arena.insert(3).index(), 1);
+    assert!(removed.iter().all(|&(id, _)| !arena.contains(id)));
+}
+
+#[test]
+fn remove_compacting_moves_the_last_entry_into_the_hole() {
+    let mut arena: Arena<i32> = (0..4).collect();
+    let ids: Vec<_> = arena.keys().collect();
+
+    let (t, moved) = arena.remove_compacting(ids[1]).unwrap();
+    assert_eq!(t, 1);
+    let (old, new) = moved.unwrap();
+    assert_eq!(old, ids[3]);
+    assert_eq!(new.index(), 1);
+    assert_ne!(new, ids[1]);
+    assert_eq!(arena[new], 3);
+    assert!(!arena.contains(ids[1]));
+    assert!(!arena.contains(ids[3]));
+    assert_eq!(arena.values().copied().collect::<Vec<_>>(), [0, 3, 2]);
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn remove_compacting_the_last_entry_moves_nothing() {
+    let mut arena: Arena<i32> = (0..3).collect();
+    let ids: Vec<_> = arena.keys().collect();
+
+    assert_eq!(arena.remove_compacting(ids[2]), Some((2, None)));
+    assert_eq!(arena[ids[0]], 0);
+    assert_eq!(arena[ids[1]], 1);
+    assert_eq!(arena.remove_compacting(ids[2]), None);
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn remove_compacting_a_retiring_slot_moves_nothing() {
+    let mut arena: Arena<i32, usize, u8> = Arena::default();
+    let mut id = arena.insert(0);
+    while id.generation() < u8::MAX - 1 {
+        arena.remove(id);
+        id = arena.insert(0);
+    }
+    let last = arena.insert(1);
+
+    assert_eq!(arena.remove_compacting(id), Some((0, None)));
+    assert_eq!(arena.retired_count(), 1);
+    assert_eq!(arena[last], 1);
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn remove_many_counts_duplicates_once() {
+    let mut arena: Arena<i32> = (0..4).collect();
+    let ids: Vec<_> = arena.keys().collect();
+    let reused = {
+        let mut scratch = arena.clone();
+        scratch.remove(ids[3]);
+        scratch.insert(30)
+    };
+
+    assert_eq!(arena.remove_many([ids[1], ids[1], ids[2], reused, ids[1]]), 2);
+    assert_eq!(arena.values().copied().collect::<Vec<_>>(), [0, 3]);
+    arena.check_invariants().unwrap();
+}
+
+#[test]
+fn retain_cross_removes_entries_whose_neighbour_is_absent() {
+    struct Node {
+        value: i32,
+        neighbour: Option<ID<Node>>,
+    }
+
+    let mut arena = Arena::new();
+    let a = arena.insert(Node { value: 0, neighbour: None });
+    let b = arena.insert(Node { value: 1, neighbour: Some(a) });
+    let gone = arena.insert(Node { value: 2, neighbour: None });
+    let c = arena.insert(Node { value: 3, neighbour: Some(gone) });
+    let d = arena.insert(Node { value: 4, neighbour: Some(b) });
+    arena.remove(gone);
+
+    arena.retain_cross(|_, node, arena| node.neighbour.is_none_or(|neighbour| arena.contains(neighbour)));
+    assert_eq!(arena.values().map(|node| node.value).collect::<Vec<_>>(), [0, 1, 4]);
+    assert!(!arena.contains(c));
+    assert!(arena.contains(d));
+}
@@ -0,0 +1,61 @@
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use typed_garena::{Arena, SecondaryMap, ID};
+
+struct Node;
+
+#[test]
+fn null_id_is_rejected_without_losing_values() {
+    let mut arena: Arena<Node> = Arena::new();
+    let id = arena.insert(Node);
+    let mut map = SecondaryMap::new();
+    map.insert(id, 1).unwrap();
+
+    let result = catch_unwind(AssertUnwindSafe(|| map.insert(ID::NULL, 2)));
+    assert!(result.is_err());
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.get(id), Some(&1));
+}
+
+#[test]
+fn clone_does_not_require_clone_marker() {
+    let mut arena: Arena<Node> = Arena::new();
+    let id = arena.insert(Node);
+    let mut map: SecondaryMap<Node, u32> = SecondaryMap::new();
+    map.insert(id, 7).unwrap();
+
+    let copy = map.clone();
+    assert_eq!(copy[id], 7);
+    assert_eq!(format!("{copy:?}"), "SecondaryMap {0: 7}");
+}
+
+#[test]
+fn insert_hands_back_values_for_stale_ids() {
+    let mut arena: Arena<Node> = Arena::new();
+    let old = arena.insert(Node);
+    arena.remove(old);
+    let new = arena.insert(Node);
+    assert_eq!(old.index(), new.index());
+
+    let mut map = SecondaryMap::new();
+    assert_eq!(map.insert(new, "new"), Ok(None));
+    assert_eq!(map.insert(old, "old"), Err("old"));
+    assert_eq!(map.get(new), Some(&"new"));
+    assert_eq!(map.get(old), None);
+    assert_eq!(map.insert(new, "newer"), Ok(Some("new")));
+    assert_eq!(map.len(), 1);
+}
+
+#[test]
+fn insert_replaces_values_of_older_generations() {
+    let mut arena: Arena<Node> = Arena::new();
+    let old = arena.insert(Node);
+    let mut map = SecondaryMap::new();
+    map.insert(old, 1).unwrap();
+
+    arena.remove(old);
+    let new = arena.insert(Node);
+    assert_eq!(map.insert(new, 2), Ok(None));
+    assert_eq!(map.get(old), None);
+    assert_eq!(map[new], 2);
+    assert_eq!(map.len(), 1);
+}
@@ -0,0 +1,188 @@
+#![cfg(feature = "serde")]
+
+use serde::{Deserialize, Serialize};
+use typed_garena::{Arena, ID};
+
+#[test]
+fn generations_serialize_like_ids() {
+    let mut arena = Arena::new();
+    let id = arena.insert("a");
+    assert_eq!(id.generation(), 0);
+
+    let json = serde_json::to_value(&arena).unwrap();
+    assert_eq!(json["entries"][0]["Occupied"][0], 0);
+    assert_eq!(json["fresh_generation"], 0);
+}
+
+#[test]
+fn arena_round_trips() {
+    let mut arena = Arena::new();
+    let a = arena.insert(1);
+    let b = arena.insert(2);
+    arena.remove(a);
+    let c = arena.insert(3);
+
+    let json = serde_json::to_string(&arena).unwrap();
+    let restored: Arena<i32> = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.get(a), None);
+    assert_eq!(restored.get(b), Some(&2));
+    assert_eq!(restored.get(c), Some(&3));
+    assert_eq!(restored, arena);
+}
+
+#[test]
+fn ids_round_trip_in_human_readable_formats() {
+    for id in [ID::<()>::NULL, ID::from_parts(3, 0), ID::from_parts(3, 7), ID::from_parts(1 << 40, 2)] {
+        let json = serde_json::to_string(&id).unwrap();
+        let restored: ID<()> = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored, id);
+    }
+    assert!(serde_json::from_str::<ID>(&serde_json::to_string(&ID::<()>::NULL).unwrap()).unwrap().is_null());
+}
+
+#[test]
+fn ids_round_trip_in_binary_formats() {
+    for id in [ID::<()>::NULL, ID::from_parts(3, 0), ID::from_parts(3, 7)] {
+        let mut words = Vec::new();
+        id.serialize(binary::Serializer(&mut words)).unwrap();
+        assert_eq!(words, [id.to_bits()]);
+        let restored = ID::deserialize(binary::Deserializer(words.into_iter())).unwrap();
+        assert_eq!(restored, id);
+    }
+
+    let mut words = Vec::new();
+    ID::<(), u32>::NULL.serialize(binary::Serializer(&mut words)).unwrap();
+    let restored = ID::<(), usize>::deserialize(binary::Deserializer(words.into_iter())).unwrap();
+    assert!(restored.is_null());
+}
+
+#[test]
+fn unpackable_ids_fail_to_serialize_in_binary_formats() {
+    let id = ID::<()>::from_parts(1 << 40, 2);
+    assert!(id.serialize(binary::Serializer(&mut Vec::new())).is_err());
+}
+
+/// A minimal non-human-readable format that only knows `u64`s, which is
+/// all an `ID` needs.
+mod binary {
+    use serde::{de, ser, ser::Impossible};
+    use std::{fmt::Display, vec::IntoIter};
+
+    #[derive(Debug)]
+    pub struct Error(String);
+    impl Display for Error {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str(&self.0)
+        }
+    }
+    impl std::error::Error for Error {}
+    impl ser::Error for Error {
+        fn custom<T: Display>(msg: T) -> Self {
+            Error(msg.to_string())
+        }
+    }
+    impl de::Error for Error {
+        fn custom<T: Display>(msg: T) -> Self {
+            Error(msg.to_string())
+        }
+    }
+
+    macro_rules! unsupported {
+        ($($method:ident($($ty:ty),*)),* $(,)?) => {
+            $(fn $method(self, $(_: $ty),*) -> Result<(), Error> {
+                Err(Error(stringify!($method).into()))
+            })*
+        };
+    }
+
+    pub struct Serializer<'a>(pub &'a mut Vec<u64>);
+    impl<'a> ser::Serializer for Serializer<'a> {
+        type Ok = ();
+        type Error = Error;
+        type SerializeSeq = Impossible<(), Error>;
+        type SerializeTuple = Impossible<(), Error>;
+        type SerializeTupleStruct = Impossible<(), Error>;
+        type SerializeTupleVariant = Impossible<(), Error>;
+        type SerializeMap = Impossible<(), Error>;
+        type SerializeStruct = Impossible<(), Error>;
+        type SerializeStructVariant = Impossible<(), Error>;
+
+        fn is_human_readable(&self) -> bool {
+            false
+        }
+        fn serialize_u64(self, v: u64) -> Result<(), Error> {
+            self.0.push(v);
+            Ok(())
+        }
+        unsupported!(
+            serialize_bool(bool), serialize_i8(i8), serialize_i16(i16), serialize_i32(i32), serialize_i64(i64),
+            serialize_u8(u8), serialize_u16(u16), serialize_u32(u32), serialize_f32(f32), serialize_f64(f64),
+            serialize_char(char), serialize_str(&str), serialize_bytes(&[u8]), serialize_none(), serialize_unit(),
+            serialize_unit_struct(&'static str), serialize_unit_variant(&'static str, u32, &'static str),
+        );
+        fn serialize_some<T: ?Sized + ser::Serialize>(self, _: &T) -> Result<(), Error> {
+            Err(Error("option".into()))
+        }
+        fn serialize_newtype_struct<T: ?Sized + ser::Serialize>(self, _: &'static str, _: &T) -> Result<(), Error> {
+            Err(Error("newtype struct".into()))
+        }
+        fn serialize_newtype_variant<T: ?Sized + ser::Serialize>(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: &T,
+        ) -> Result<(), Error> {
+            Err(Error("newtype variant".into()))
+        }
+        fn serialize_seq(self, _: Option<usize>) -> Result<Self::SerializeSeq, Error> {
+            Err(Error("seq".into()))
+        }
+        fn serialize_tuple(self, _: usize) -> Result<Self::SerializeTuple, Error> {
+            Err(Error("tuple".into()))
+        }
+        fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeTupleStruct, Error> {
+            Err(Error("tuple struct".into()))
+        }
+        fn serialize_tuple_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeTupleVariant, Error> {
+            Err(Error("tuple variant".into()))
+        }
+        fn serialize_map(self, _: Option<usize>) -> Result<Self::SerializeMap, Error> {
+            Err(Error("map".into()))
+        }
+        fn serialize_struct(self, _: &'static str, _: usize) -> Result<Self::SerializeStruct, Error> {
+            Err(Error("struct".into()))
+        }
+        fn serialize_struct_variant(
+            self,
+            _: &'static str,
+            _: u32,
+            _: &'static str,
+            _: usize,
+        ) -> Result<Self::SerializeStructVariant, Error> {
+            Err(Error("struct variant".into()))
+        }
+    }
+
+    pub struct Deserializer(pub IntoIter<u64>);
+    impl<'de> de::Deserializer<'de> for Deserializer {
+        type Error = Error;
+
+        fn is_human_readable(&self) -> bool {
+            false
+        }
+        fn deserialize_any<V: de::Visitor<'de>>(mut self, visitor: V) -> Result<V::Value, Error> {
+            visitor.visit_u64(self.0.next().ok_or_else(|| Error("end of input".into()))?)
+        }
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string bytes byte_buf option unit
+            unit_struct newtype_struct seq tuple tuple_struct map struct enum identifier ignored_any
+        }
+    }
+}